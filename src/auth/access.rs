@@ -40,7 +40,13 @@ impl Client {
     pub fn group_access(&self) -> Result<Vec<GroupAccess<GlobalPermission>>, Error> {
         let url = "rest/api/1.0/admin/permissions/groups";
 
-        self.get_paged(&url)
+        self.get_paged(url)
+    }
+
+    pub fn user_access(&self) -> Result<Vec<UserAccess<GlobalPermission>>, Error> {
+        let url = "rest/api/1.0/admin/permissions/users";
+
+        self.get_paged(url)
     }
 
     pub fn project_group_access(
@@ -58,14 +64,14 @@ impl Client {
         groups: Vec<GroupAccess<ProjectPermission>>,
     ) -> Result<(), Error> {
         let new_groups: HashSet<GroupAccess<ProjectPermission>> = HashSet::from_iter(groups);
-        let current_groups = HashSet::from_iter(self.project_group_access(&project_key)?);
+        let current_groups = HashSet::from_iter(self.project_group_access(project_key)?);
 
         for group in current_groups.difference(&new_groups) {
             println!("Deleting {}", group);
             let url = format!(
                 "rest/api/1.0/projects/{}/permissions/groups?name={}",
                 project_key,
-                client::percent_encode(&group.group().name())
+                client::percent_encode(group.group().name())
             );
             self.delete(&url)?;
         }
@@ -105,7 +111,7 @@ impl Client {
     ) -> Result<(), Error> {
         let new_groups: HashSet<GroupAccess<RepositoryPermission>> = HashSet::from_iter(groups);
         let current_groups =
-            HashSet::from_iter(self.repository_group_access(&project_key, &repository_slug)?);
+            HashSet::from_iter(self.repository_group_access(project_key, repository_slug)?);
 
         for group in current_groups.difference(&new_groups) {
             println!("Deleting {}", group);
@@ -113,7 +119,7 @@ impl Client {
                 "rest/api/1.0/projects/{}/repos/{}/permissions/groups?name={}",
                 project_key,
                 repository_slug,
-                client::percent_encode(&group.group().name())
+                client::percent_encode(group.group().name())
             );
             self.delete(&url)?;
         }
@@ -176,14 +182,14 @@ impl Client {
         users: Vec<UserAccess<ProjectPermission>>,
     ) -> Result<(), Error> {
         let new_users: HashSet<UserAccess<ProjectPermission>> = HashSet::from_iter(users);
-        let existing_users = HashSet::from_iter(self.project_user_access(&project_key)?);
+        let existing_users = HashSet::from_iter(self.project_user_access(project_key)?);
 
         for user in existing_users.difference(&new_users) {
             println!("Deleting {}", user);
             let url = format!(
                 "rest/api/1.0/projects/{}/permissions/users?name={}",
                 project_key,
-                client::percent_encode(&user.user().name())
+                client::percent_encode(user.user().name())
             );
             self.delete(&url)?;
         }
@@ -223,7 +229,7 @@ impl Client {
     ) -> Result<(), Error> {
         let new_users: HashSet<UserAccess<RepositoryPermission>> = HashSet::from_iter(users);
         let existing_users =
-            HashSet::from_iter(self.repository_user_access(&project_key, &repository_slug)?);
+            HashSet::from_iter(self.repository_user_access(project_key, repository_slug)?);
 
         for user in existing_users.difference(&new_users) {
             println!("Deleting {}", user);
@@ -231,7 +237,7 @@ impl Client {
                 "rest/api/1.0/projects/{}/repos/{}/permissions/users?name={}",
                 project_key,
                 repository_slug,
-                client::percent_encode(&user.user().name())
+                client::percent_encode(user.user().name())
             );
             self.delete(&url)?;
         }
@@ -256,6 +262,7 @@ impl Client {
 #[serde(rename_all = "camelCase")]
 pub struct RepositoryKeyAccess {
     key: SshKey,
+    #[allow(dead_code)]
     repository: Repository,
     permission: String,
 }
@@ -270,6 +277,7 @@ impl fmt::Display for RepositoryKeyAccess {
 #[serde(rename_all = "camelCase")]
 pub struct ProjectKeyAccess {
     key: SshKey,
+    #[allow(dead_code)]
     project: Project,
     permission: String,
 }
@@ -301,11 +309,12 @@ impl Client {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SshKey {
     id: u64,
     text: String,
+    #[serde(default)]
     label: String,
 }
 
@@ -314,3 +323,17 @@ impl fmt::Display for SshKey {
         write!(f, "{}", self.label)
     }
 }
+
+impl SshKey {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
@@ -0,0 +1,294 @@
+use auth::access::SshKey;
+use client::Client;
+use base64;
+use failure::Error;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use url::form_urlencoded;
+
+pub const MIN_RSA_BITS: u32 = 2048;
+
+impl SshKey {
+    /// The key type named in the key text, such as `ssh-rsa`.
+    pub fn algorithm(&self) -> Option<&str> {
+        self.text().split_whitespace().next()
+    }
+
+    fn blob(&self) -> Result<Vec<u8>, Error> {
+        match self.text().split_whitespace().nth(1) {
+            Some(encoded) => Ok(base64::decode(encoded)?),
+            None => bail!("SSH key {} has no key data", self),
+        }
+    }
+
+    /// The OpenSSH-style `SHA256:` fingerprint of the key.
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        let digest = Sha256::digest(&self.blob()?);
+
+        Ok(format!(
+            "SHA256:{}",
+            base64::encode_config(&digest, base64::STANDARD_NO_PAD)
+        ))
+    }
+
+    /// The key size in bits, where it can be determined from the key type.
+    pub fn bit_length(&self) -> Result<Option<u32>, Error> {
+        let blob = self.blob()?;
+        let mut fields = BlobFields { blob: &blob };
+        let algorithm = fields.next_field()?;
+
+        match algorithm {
+            b"ssh-rsa" => {
+                let _exponent = fields.next_field()?;
+                Ok(Some(mpint_bits(fields.next_field()?)))
+            }
+            b"ssh-ed25519" => Ok(Some(256)),
+            b"ecdsa-sha2-nistp256" => Ok(Some(256)),
+            b"ecdsa-sha2-nistp384" => Ok(Some(384)),
+            b"ecdsa-sha2-nistp521" => Ok(Some(521)),
+            _ => Ok(None),
+        }
+    }
+
+    /// RSA keys shorter than [`MIN_RSA_BITS`].
+    pub fn is_weak(&self) -> Result<bool, Error> {
+        Ok(self.algorithm() == Some("ssh-rsa")
+            && self.bit_length()?.map_or(false, |bits| bits < MIN_RSA_BITS))
+    }
+}
+
+struct BlobFields<'a> {
+    blob: &'a [u8],
+}
+
+impl<'a> BlobFields<'a> {
+    fn next_field(&mut self) -> Result<&'a [u8], Error> {
+        if self.blob.len() < 4 {
+            bail!("truncated SSH key data");
+        }
+        let (length, rest) = self.blob.split_at(4);
+        let length = length
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+        if rest.len() < length {
+            bail!("truncated SSH key data");
+        }
+        let (field, rest) = rest.split_at(length);
+        self.blob = rest;
+
+        Ok(field)
+    }
+}
+
+fn mpint_bits(mpint: &[u8]) -> u32 {
+    match mpint.iter().position(|b| *b != 0) {
+        Some(i) => (mpint.len() - i) as u32 * 8 - mpint[i].leading_zeros(),
+        None => 0,
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct NewSshKey<'a> {
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GpgSubKey {
+    fingerprint: String,
+    expiry_date: Option<u64>,
+}
+
+impl GpgSubKey {
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    pub fn expiry_date(&self) -> Option<u64> {
+        self.expiry_date
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GpgKey {
+    id: String,
+    fingerprint: String,
+    email_address: Option<String>,
+    expiry_date: Option<u64>,
+    #[serde(default)]
+    sub_keys: Vec<GpgSubKey>,
+    text: Option<String>,
+}
+
+impl fmt::Display for GpgKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.key_id())
+    }
+}
+
+impl GpgKey {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// The long key ID: the last 16 hex digits of the fingerprint.
+    pub fn key_id(&self) -> &str {
+        let start = self.fingerprint.len().saturating_sub(16);
+        &self.fingerprint[start..]
+    }
+
+    pub fn email_address(&self) -> Option<&str> {
+        self.email_address.as_ref().map(String::as_str)
+    }
+
+    pub fn expiry_date(&self) -> Option<u64> {
+        self.expiry_date
+    }
+
+    pub fn sub_keys(&self) -> &[GpgSubKey] {
+        &self.sub_keys
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_ref().map(String::as_str)
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct NewGpgKey<'a> {
+    text: &'a str,
+}
+
+/// A key together with the slug of the user it belongs to.
+#[derive(Clone, Debug)]
+pub struct UserSshKey {
+    pub user_slug: String,
+    pub key: SshKey,
+}
+
+impl fmt::Display for UserSshKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.key, self.user_slug)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SshKeyAudit {
+    /// Keys registered more than once, grouped by fingerprint.
+    pub duplicates: Vec<(String, Vec<UserSshKey>)>,
+    pub weak: Vec<UserSshKey>,
+    /// Keys whose text could not be parsed.
+    pub invalid: Vec<UserSshKey>,
+}
+
+impl SshKeyAudit {
+    pub fn from_keys(keys: Vec<UserSshKey>) -> SshKeyAudit {
+        let mut audit = SshKeyAudit::default();
+        let mut by_fingerprint: HashMap<String, Vec<UserSshKey>> = HashMap::new();
+
+        for key in keys {
+            let (fingerprint, weak) = match (key.key.fingerprint(), key.key.is_weak()) {
+                (Ok(fingerprint), Ok(weak)) => (fingerprint, weak),
+                _ => {
+                    audit.invalid.push(key);
+                    continue;
+                }
+            };
+
+            if weak {
+                audit.weak.push(key.clone());
+            }
+            by_fingerprint.entry(fingerprint).or_default().push(key);
+        }
+
+        audit.duplicates = by_fingerprint
+            .into_iter()
+            .filter(|(_, keys)| keys.len() > 1)
+            .collect();
+        audit.duplicates.sort_by(|a, b| a.0.cmp(&b.0));
+
+        audit
+    }
+}
+
+fn user_query(user_slug: &str) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .append_pair("user", user_slug)
+        .finish()
+}
+
+impl Client {
+    pub fn user_ssh_keys(&self, user_slug: &str) -> Result<Vec<SshKey>, Error> {
+        let url = format!("rest/ssh/1.0/keys?{}", user_query(user_slug));
+
+        self.get_paged(&url)
+    }
+
+    pub fn add_user_ssh_key(
+        &self,
+        user_slug: &str,
+        text: &str,
+        label: Option<&str>,
+    ) -> Result<SshKey, Error> {
+        let url = format!("rest/ssh/1.0/keys?{}", user_query(user_slug));
+
+        self.post(&url, Some(&NewSshKey { text, label }))
+    }
+
+    pub fn delete_user_ssh_key(&self, key_id: u64) -> Result<(), Error> {
+        let url = format!("rest/ssh/1.0/keys/{}", key_id);
+
+        self.delete(&url)
+    }
+
+    pub fn user_gpg_keys(&self, user_slug: &str) -> Result<Vec<GpgKey>, Error> {
+        let url = format!("rest/gpg/1.0/keys?{}", user_query(user_slug));
+
+        self.get_paged(&url)
+    }
+
+    pub fn add_user_gpg_key(&self, user_slug: &str, text: &str) -> Result<GpgKey, Error> {
+        let url = format!("rest/gpg/1.0/keys?{}", user_query(user_slug));
+
+        self.post(&url, Some(&NewGpgKey { text }))
+    }
+
+    /// `key` may be the key's ID or fingerprint.
+    pub fn delete_user_gpg_key(&self, key: &str) -> Result<(), Error> {
+        let url = format!("rest/gpg/1.0/keys/{}", key);
+
+        self.delete(&url)
+    }
+
+    pub fn ssh_key_audit(&self) -> Result<SshKeyAudit, Error> {
+        let keys: Result<Vec<Vec<UserSshKey>>, Error> = self
+            .users_admin()?
+            .into_par_iter()
+            .map(|user| {
+                Ok(self
+                    .user_ssh_keys(user.slug())?
+                    .into_iter()
+                    .map(|key| UserSshKey {
+                        user_slug: user.slug().to_owned(),
+                        key,
+                    })
+                    .collect())
+            })
+            .collect();
+
+        Ok(SshKeyAudit::from_keys(
+            keys?.into_iter().flatten().collect(),
+        ))
+    }
+}
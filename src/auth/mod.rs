@@ -1,2 +1,5 @@
 pub mod access;
+pub mod key;
 pub mod permission;
+pub mod sweep;
+pub mod token;
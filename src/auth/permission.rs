@@ -113,7 +113,10 @@ impl<'de> Deserialize<'de> for RepositoryPermission {
 pub enum GlobalPermission {
     Read,
     Write,
+    LicensedUser,
+    ProjectCreate,
     Admin,
+    SysAdmin,
 }
 
 impl FromStr for GlobalPermission {
@@ -123,7 +126,10 @@ impl FromStr for GlobalPermission {
         match s {
             "READ" => Ok(GlobalPermission::Read),
             "WRITE" => Ok(GlobalPermission::Write),
+            "LICENSED_USER" => Ok(GlobalPermission::LicensedUser),
+            "PROJECT_CREATE" => Ok(GlobalPermission::ProjectCreate),
             "ADMIN" => Ok(GlobalPermission::Admin),
+            "SYS_ADMIN" => Ok(GlobalPermission::SysAdmin),
             _ => bail!("unexpected permission {}", s),
         }
     }
@@ -134,7 +140,10 @@ impl fmt::Display for GlobalPermission {
         match *self {
             GlobalPermission::Read => write!(f, "READ"),
             GlobalPermission::Write => write!(f, "WRITE"),
+            GlobalPermission::LicensedUser => write!(f, "LICENSED_USER"),
+            GlobalPermission::ProjectCreate => write!(f, "PROJECT_CREATE"),
             GlobalPermission::Admin => write!(f, "ADMIN"),
+            GlobalPermission::SysAdmin => write!(f, "SYS_ADMIN"),
         }
     }
 }
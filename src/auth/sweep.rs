@@ -0,0 +1,335 @@
+use auth::access::{GroupAccess, UserAccess};
+use auth::permission::{ProjectPermission, RepositoryPermission};
+use client::Client;
+use failure::Error;
+use group::Group;
+use rayon::prelude::*;
+use repository::Repository;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SweepReason {
+    InactiveUser,
+    MissingGroup,
+    /// The principal already holds an equal or stronger project permission.
+    InheritedFromProject,
+}
+
+impl fmt::Display for SweepReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SweepReason::InactiveUser => write!(f, "user is inactive"),
+            SweepReason::MissingGroup => write!(f, "group no longer exists"),
+            SweepReason::InheritedFromProject => write!(f, "inherited from project"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Removal<A> {
+    pub access: A,
+    pub reason: SweepReason,
+}
+
+impl<A: fmt::Display> fmt::Display for Removal<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.access, self.reason)
+    }
+}
+
+/// The grants to keep on a project or repository, and those to remove.
+#[derive(Debug)]
+pub struct AccessPlan<P> {
+    pub users: Vec<UserAccess<P>>,
+    pub groups: Vec<GroupAccess<P>>,
+    pub user_removals: Vec<Removal<UserAccess<P>>>,
+    pub group_removals: Vec<Removal<GroupAccess<P>>>,
+}
+
+impl<P> AccessPlan<P> {
+    fn new(
+        users: Vec<UserAccess<P>>,
+        groups: Vec<GroupAccess<P>>,
+        user_reason: impl Fn(&UserAccess<P>) -> Option<SweepReason>,
+        group_reason: impl Fn(&GroupAccess<P>) -> Option<SweepReason>,
+    ) -> AccessPlan<P> {
+        let mut plan = AccessPlan {
+            users: Vec::new(),
+            groups: Vec::new(),
+            user_removals: Vec::new(),
+            group_removals: Vec::new(),
+        };
+
+        for access in users {
+            match user_reason(&access) {
+                Some(reason) => plan.user_removals.push(Removal { access, reason }),
+                None => plan.users.push(access),
+            }
+        }
+
+        for access in groups {
+            match group_reason(&access) {
+                Some(reason) => plan.group_removals.push(Removal { access, reason }),
+                None => plan.groups.push(access),
+            }
+        }
+
+        plan
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.user_removals.is_empty() && self.group_removals.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct ProjectSweep {
+    pub project_key: String,
+    pub plan: AccessPlan<ProjectPermission>,
+}
+
+#[derive(Debug)]
+pub struct RepositorySweep {
+    pub project_key: String,
+    pub repository_slug: String,
+    pub plan: AccessPlan<RepositoryPermission>,
+}
+
+/// Only projects and repositories with something to remove are included.
+#[derive(Debug, Default)]
+pub struct AccessSweep {
+    pub projects: Vec<ProjectSweep>,
+    pub repositories: Vec<RepositorySweep>,
+}
+
+impl AccessSweep {
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty() && self.repositories.is_empty()
+    }
+}
+
+fn project_rank(permission: &ProjectPermission) -> u8 {
+    match permission {
+        ProjectPermission::Read => 1,
+        ProjectPermission::Write => 2,
+        ProjectPermission::Admin => 3,
+    }
+}
+
+fn repository_rank(permission: &RepositoryPermission) -> u8 {
+    match permission {
+        RepositoryPermission::Read => 1,
+        RepositoryPermission::Write => 2,
+        RepositoryPermission::Admin => 3,
+    }
+}
+
+fn inherited(
+    project_ranks: &HashMap<String, u8>,
+    name: &str,
+    permission: &RepositoryPermission,
+) -> bool {
+    project_ranks
+        .get(name)
+        .map_or(false, |rank| *rank >= repository_rank(permission))
+}
+
+fn user_reason<P>(access: &UserAccess<P>) -> Option<SweepReason> {
+    if access.user().active() {
+        None
+    } else {
+        Some(SweepReason::InactiveUser)
+    }
+}
+
+fn group_reason<P>(groups: &HashSet<String>, access: &GroupAccess<P>) -> Option<SweepReason> {
+    if groups.contains(access.group().name()) {
+        None
+    } else {
+        Some(SweepReason::MissingGroup)
+    }
+}
+
+/// The current grants minus those flagged by the sweep. Grants whose
+/// permission has changed since the sweep are no longer the flagged grant
+/// and are kept.
+fn kept_users<P: PartialEq>(
+    current: Vec<UserAccess<P>>,
+    removals: &[Removal<UserAccess<P>>],
+) -> Vec<UserAccess<P>> {
+    current
+        .into_iter()
+        .filter(|a| {
+            !removals.iter().any(|r| {
+                r.access.user().name() == a.user().name() && r.access.permission() == a.permission()
+            })
+        })
+        .collect()
+}
+
+fn kept_groups<P: PartialEq>(
+    current: Vec<GroupAccess<P>>,
+    removals: &[Removal<GroupAccess<P>>],
+) -> Vec<GroupAccess<P>> {
+    current
+        .into_iter()
+        .filter(|a| {
+            !removals.iter().any(|r| {
+                r.access.group().name() == a.group().name()
+                    && r.access.permission() == a.permission()
+            })
+        })
+        .collect()
+}
+
+impl Client {
+    fn sweep_repository(
+        &self,
+        repository: &Repository,
+        groups: &HashSet<String>,
+        project_users: &HashMap<String, u8>,
+        project_groups: &HashMap<String, u8>,
+    ) -> Result<RepositorySweep, Error> {
+        let project_key = &repository.project.key;
+        let plan = AccessPlan::new(
+            self.repository_user_access(project_key, repository.slug())?,
+            self.repository_group_access(project_key, repository.slug())?,
+            |access| {
+                user_reason(access).or_else(|| {
+                    if inherited(project_users, access.user().name(), access.permission()) {
+                        Some(SweepReason::InheritedFromProject)
+                    } else {
+                        None
+                    }
+                })
+            },
+            |access| {
+                group_reason(groups, access).or_else(|| {
+                    if inherited(project_groups, access.group().name(), access.permission()) {
+                        Some(SweepReason::InheritedFromProject)
+                    } else {
+                        None
+                    }
+                })
+            },
+        );
+
+        Ok(RepositorySweep {
+            project_key: project_key.clone(),
+            repository_slug: repository.slug().to_owned(),
+            plan,
+        })
+    }
+
+    fn sweep_project(
+        &self,
+        project_key: &str,
+        groups: &HashSet<String>,
+    ) -> Result<AccessSweep, Error> {
+        let users = self.project_user_access(project_key)?;
+        let project_groups = self.project_group_access(project_key)?;
+
+        // Inactive users and missing groups are removed from the project too,
+        // so repository grants are only redundant with grants that survive.
+        let plan = AccessPlan::new(users, project_groups, user_reason, |access| {
+            group_reason(groups, access)
+        });
+
+        let user_ranks: HashMap<String, u8> = plan
+            .users
+            .iter()
+            .map(|a| (a.user().name().to_owned(), project_rank(a.permission())))
+            .collect();
+        let group_ranks: HashMap<String, u8> = plan
+            .groups
+            .iter()
+            .map(|a| (a.group().name().to_owned(), project_rank(a.permission())))
+            .collect();
+
+        let repositories: Result<Vec<RepositorySweep>, Error> = self
+            .repositories(project_key)?
+            .par_iter()
+            .map(|repository| self.sweep_repository(repository, groups, &user_ranks, &group_ranks))
+            .collect();
+
+        let mut sweep = AccessSweep::default();
+        if !plan.is_empty() {
+            sweep.projects.push(ProjectSweep {
+                project_key: project_key.to_owned(),
+                plan,
+            });
+        }
+        sweep.repositories = repositories?
+            .into_iter()
+            .filter(|r| !r.plan.is_empty())
+            .collect();
+
+        Ok(sweep)
+    }
+
+    pub fn access_sweep(&self) -> Result<AccessSweep, Error> {
+        let groups: HashSet<String> = self
+            .groups_admin(None)?
+            .iter()
+            .map(Group::name)
+            .map(str::to_owned)
+            .collect();
+
+        let sweeps: Result<Vec<AccessSweep>, Error> = self
+            .projects()?
+            .par_iter()
+            .map(|project| self.sweep_project(project.key(), &groups))
+            .collect();
+
+        let mut sweep = AccessSweep::default();
+        for mut project in sweeps? {
+            sweep.projects.append(&mut project.projects);
+            sweep.repositories.append(&mut project.repositories);
+        }
+
+        Ok(sweep)
+    }
+
+    /// Applies a sweep through the `set_*_access` methods. Grants are read
+    /// again first so that only the flagged grants are removed, even if
+    /// others were added or changed since the sweep.
+    pub fn apply_access_sweep(&self, sweep: AccessSweep) -> Result<(), Error> {
+        for project in sweep.projects {
+            let key = &project.project_key;
+            let plan = project.plan;
+            if !plan.user_removals.is_empty() {
+                let current = self.project_user_access(key)?;
+                self.set_project_user_access(key, kept_users(current, &plan.user_removals))?;
+            }
+            if !plan.group_removals.is_empty() {
+                let current = self.project_group_access(key)?;
+                self.set_project_group_access(key, kept_groups(current, &plan.group_removals))?;
+            }
+        }
+
+        for repository in sweep.repositories {
+            let key = &repository.project_key;
+            let slug = &repository.repository_slug;
+            let plan = repository.plan;
+            if !plan.user_removals.is_empty() {
+                let current = self.repository_user_access(key, slug)?;
+                self.set_repository_user_access(
+                    key,
+                    slug,
+                    kept_users(current, &plan.user_removals),
+                )?;
+            }
+            if !plan.group_removals.is_empty() {
+                let current = self.repository_group_access(key, slug)?;
+                self.set_repository_group_access(
+                    key,
+                    slug,
+                    kept_groups(current, &plan.group_removals),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
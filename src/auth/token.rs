@@ -0,0 +1,222 @@
+use client::Client;
+use failure::Error;
+use reqwest::Method;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use user::User;
+
+pub(crate) const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TokenPermission {
+    ProjectRead,
+    ProjectWrite,
+    ProjectAdmin,
+    RepoRead,
+    RepoWrite,
+    RepoAdmin,
+}
+
+impl fmt::Display for TokenPermission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenPermission::ProjectRead => write!(f, "PROJECT_READ"),
+            TokenPermission::ProjectWrite => write!(f, "PROJECT_WRITE"),
+            TokenPermission::ProjectAdmin => write!(f, "PROJECT_ADMIN"),
+            TokenPermission::RepoRead => write!(f, "REPO_READ"),
+            TokenPermission::RepoWrite => write!(f, "REPO_WRITE"),
+            TokenPermission::RepoAdmin => write!(f, "REPO_ADMIN"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenExpiry {
+    Never,
+    /// Milliseconds since the epoch.
+    At(u64),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessToken {
+    id: String,
+    name: String,
+    created_date: u64,
+    last_authenticated: Option<u64>,
+    expiry_date: Option<u64>,
+    #[serde(default)]
+    permissions: Vec<TokenPermission>,
+    user: User,
+    /// Only present in the response to creating a token.
+    token: Option<String>,
+}
+
+impl fmt::Display for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.user)
+    }
+}
+
+impl AccessToken {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn created_date(&self) -> u64 {
+        self.created_date
+    }
+
+    pub fn last_authenticated(&self) -> Option<u64> {
+        self.last_authenticated
+    }
+
+    pub fn expiry(&self) -> TokenExpiry {
+        match self.expiry_date {
+            Some(date) => TokenExpiry::At(date),
+            None => TokenExpiry::Never,
+        }
+    }
+
+    pub fn permissions(&self) -> &[TokenPermission] {
+        &self.permissions
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_ref().map(String::as_str)
+    }
+
+    /// Whether the token expires (or has expired) before `days` from `now`,
+    /// given in milliseconds since the epoch.
+    pub fn expires_within(&self, days: u64, now: u64) -> bool {
+        match self.expiry() {
+            TokenExpiry::At(date) => {
+                date <= now.saturating_add(days.saturating_mul(MILLIS_PER_DAY))
+            }
+            TokenExpiry::Never => false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAccessToken {
+    pub name: String,
+    pub permissions: Vec<TokenPermission>,
+    /// `None` creates a token that never expires, where the server allows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_days: Option<u32>,
+}
+
+impl NewAccessToken {
+    pub fn new(name: &str, permissions: &[TokenPermission]) -> NewAccessToken {
+        NewAccessToken {
+            name: name.to_owned(),
+            permissions: permissions.to_vec(),
+            expiry_days: None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AccessTokenUpdate<'a> {
+    name: &'a str,
+    permissions: &'a [TokenPermission],
+}
+
+pub(crate) fn now_millis() -> Result<u64, Error> {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+    Ok(elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis()))
+}
+
+impl Client {
+    pub fn access_tokens(&self, user_slug: &str) -> Result<Vec<AccessToken>, Error> {
+        let url = format!("rest/access-tokens/1.0/users/{}", user_slug);
+
+        self.get_paged(&url)
+    }
+
+    pub fn access_token(&self, user_slug: &str, token_id: &str) -> Result<AccessToken, Error> {
+        let url = format!("rest/access-tokens/1.0/users/{}/{}", user_slug, token_id);
+
+        self.get(&url)
+    }
+
+    /// The returned token carries the secret, which cannot be fetched again.
+    pub fn create_access_token(
+        &self,
+        user_slug: &str,
+        token: &NewAccessToken,
+    ) -> Result<AccessToken, Error> {
+        let url = format!("rest/access-tokens/1.0/users/{}", user_slug);
+
+        self.send(Method::PUT, &url, Some(token))
+    }
+
+    pub fn update_access_token(
+        &self,
+        user_slug: &str,
+        token_id: &str,
+        name: &str,
+        permissions: &[TokenPermission],
+    ) -> Result<AccessToken, Error> {
+        let url = format!("rest/access-tokens/1.0/users/{}/{}", user_slug, token_id);
+        let update = AccessTokenUpdate { name, permissions };
+
+        self.post(&url, Some(&update))
+    }
+
+    pub fn revoke_access_token(&self, user_slug: &str, token_id: &str) -> Result<(), Error> {
+        let url = format!("rest/access-tokens/1.0/users/{}/{}", user_slug, token_id);
+
+        self.delete(&url)
+    }
+
+    pub fn expiring_access_tokens(
+        &self,
+        user_slug: &str,
+        days: u64,
+    ) -> Result<Vec<AccessToken>, Error> {
+        let now = now_millis()?;
+
+        Ok(self
+            .access_tokens(user_slug)?
+            .into_iter()
+            .filter(|t| t.expires_within(days, now))
+            .collect())
+    }
+
+    /// Creates a replacement with the same name and permissions, then revokes
+    /// `token`. The replacement is returned with its secret alongside the
+    /// outcome of the revocation, so the secret is not lost if that fails.
+    pub fn rotate_access_token(
+        &self,
+        user_slug: &str,
+        token: &AccessToken,
+        expiry_days: Option<u32>,
+    ) -> Result<(AccessToken, Result<(), Error>), Error> {
+        let replacement = NewAccessToken {
+            name: token.name.clone(),
+            permissions: token.permissions.clone(),
+            expiry_days,
+        };
+
+        println!("Adding {}", token);
+        let created = self.create_access_token(user_slug, &replacement)?;
+        println!("Deleting {}", token);
+        let revoked = self.revoke_access_token(user_slug, &token.id);
+
+        Ok((created, revoked))
+    }
+}
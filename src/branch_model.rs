@@ -0,0 +1,196 @@
+use client::Client;
+use failure::Error;
+use ref_matcher::{qualify, unqualify};
+use reqwest::Method;
+use std::fmt;
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchModel {
+    pub development: Option<ModelBranch>,
+    pub production: Option<ModelBranch>,
+    #[serde(default)]
+    pub types: Vec<BranchType>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelBranch {
+    pub id: String,
+    pub display_id: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchType {
+    pub id: BranchCategory,
+    pub display_name: String,
+    pub prefix: String,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BranchCategory {
+    Bugfix,
+    Feature,
+    Hotfix,
+    Release,
+}
+
+impl BranchModel {
+    pub fn classify(&self, branch_name: &str) -> Option<BranchCategory> {
+        let name = unqualify(branch_name);
+
+        self.types
+            .iter()
+            .filter(|t| !t.prefix.is_empty() && name.starts_with(&t.prefix))
+            .max_by_key(|t| t.prefix.len())
+            .map(|t| t.id)
+    }
+}
+
+impl fmt::Display for BranchCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BranchCategory::Bugfix => write!(f, "BUGFIX"),
+            BranchCategory::Feature => write!(f, "FEATURE"),
+            BranchCategory::Hotfix => write!(f, "HOTFIX"),
+            BranchCategory::Release => write!(f, "RELEASE"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchModelConfiguration {
+    pub development: BranchConfiguration,
+    pub production: Option<BranchConfiguration>,
+    #[serde(default)]
+    pub types: Vec<BranchTypeConfiguration>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchConfiguration {
+    pub ref_id: Option<String>,
+    pub use_default: bool,
+}
+
+impl BranchConfiguration {
+    pub fn default_branch() -> BranchConfiguration {
+        BranchConfiguration {
+            ref_id: None,
+            use_default: true,
+        }
+    }
+
+    pub fn branch(name: &str) -> BranchConfiguration {
+        BranchConfiguration {
+            ref_id: Some(qualify(name)),
+            use_default: false,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchTypeConfiguration {
+    pub id: BranchCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub enabled: bool,
+    pub prefix: String,
+}
+
+impl BranchTypeConfiguration {
+    pub fn new(id: BranchCategory, prefix: &str) -> BranchTypeConfiguration {
+        BranchTypeConfiguration {
+            id,
+            display_name: None,
+            enabled: true,
+            prefix: prefix.to_owned(),
+        }
+    }
+}
+
+impl Client {
+    pub fn project_branch_model_configuration(
+        &self,
+        project_key: &str,
+    ) -> Result<BranchModelConfiguration, Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/branchmodel/configuration",
+            project_key
+        );
+
+        self.get(&url)
+    }
+
+    pub fn set_project_branch_model_configuration(
+        &self,
+        project_key: &str,
+        configuration: &BranchModelConfiguration,
+    ) -> Result<BranchModelConfiguration, Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/branchmodel/configuration",
+            project_key
+        );
+
+        self.send(Method::PUT, &url, Some(configuration))
+    }
+
+    pub fn repository_branch_model_configuration(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<BranchModelConfiguration, Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/repos/{}/branchmodel/configuration",
+            project_key, repository_slug
+        );
+
+        self.get(&url)
+    }
+
+    pub fn set_repository_branch_model_configuration(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        configuration: &BranchModelConfiguration,
+    ) -> Result<BranchModelConfiguration, Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/repos/{}/branchmodel/configuration",
+            project_key, repository_slug
+        );
+
+        self.send(Method::PUT, &url, Some(configuration))
+    }
+
+    /// Removes the repository's own configuration so it inherits the
+    /// project's branching model again.
+    pub fn inherit_repository_branch_model_configuration(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/repos/{}/branchmodel/configuration",
+            project_key, repository_slug
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn repository_branch_model(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<BranchModel, Error> {
+        let url = format!(
+            "rest/branch-utils/1.0/projects/{}/repos/{}/branchmodel",
+            project_key, repository_slug
+        );
+
+        self.get(&url)
+    }
+}
@@ -0,0 +1,370 @@
+use auth::access::SshKey;
+use client::Client;
+use failure::Error;
+use itertools::Itertools;
+use reconcile::reconcile;
+use ref_matcher::RefMatcher;
+use std::collections::HashSet;
+use std::fmt;
+use user::User;
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestrictionType {
+    ReadOnly,
+    NoDeletes,
+    FastForwardOnly,
+    PullRequestOnly,
+}
+
+impl fmt::Display for RestrictionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RestrictionType::ReadOnly => write!(f, "read-only"),
+            RestrictionType::NoDeletes => write!(f, "no-deletes"),
+            RestrictionType::FastForwardOnly => write!(f, "fast-forward-only"),
+            RestrictionType::PullRequestOnly => write!(f, "pull-request-only"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefRestriction {
+    id: u64,
+    #[serde(rename = "type")]
+    restriction_type: RestrictionType,
+    matcher: RefMatcher,
+    #[serde(default)]
+    users: Vec<User>,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    access_keys: Vec<AccessKey>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessKey {
+    key: SshKey,
+}
+
+impl AccessKey {
+    pub fn key(&self) -> &SshKey {
+        &self.key
+    }
+}
+
+impl fmt::Display for RefRestriction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} on {}", self.restriction_type, self.matcher)
+    }
+}
+
+impl RefRestriction {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn restriction_type(&self) -> RestrictionType {
+        self.restriction_type
+    }
+
+    pub fn matcher(&self) -> &RefMatcher {
+        &self.matcher
+    }
+
+    pub fn users(&self) -> &[User] {
+        &self.users
+    }
+
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    pub fn access_keys(&self) -> &[AccessKey] {
+        &self.access_keys
+    }
+
+    fn applies_to(&self, restriction: &NewRefRestriction) -> bool {
+        self.restriction_type == restriction.restriction_type
+            && self.matcher.same_ref(&restriction.matcher)
+    }
+
+    fn satisfies(&self, restriction: &NewRefRestriction) -> bool {
+        let users: HashSet<&str> = self.users.iter().map(User::name).collect();
+        let groups: HashSet<&str> = self.groups.iter().map(String::as_str).collect();
+        let access_keys: HashSet<u64> = self.access_keys.iter().map(|k| k.key.id()).collect();
+
+        self.applies_to(restriction)
+            && users == restriction.users.iter().map(String::as_str).collect()
+            && groups == restriction.groups.iter().map(String::as_str).collect()
+            && access_keys == restriction.access_keys.iter().cloned().collect()
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRefRestriction {
+    #[serde(rename = "type")]
+    pub restriction_type: RestrictionType,
+    pub matcher: RefMatcher,
+    pub users: Vec<String>,
+    pub groups: Vec<String>,
+    pub access_keys: Vec<u64>,
+}
+
+impl NewRefRestriction {
+    pub fn new(restriction_type: RestrictionType, matcher: RefMatcher) -> NewRefRestriction {
+        NewRefRestriction {
+            restriction_type,
+            matcher,
+            users: Vec::new(),
+            groups: Vec::new(),
+            access_keys: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for NewRefRestriction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} on {}", self.restriction_type, self.matcher)?;
+
+        let exemptions = self
+            .users
+            .iter()
+            .chain(self.groups.iter())
+            .map(String::to_owned)
+            .chain(self.access_keys.iter().map(|k| format!("key {}", k)))
+            .join(", ");
+        if !exemptions.is_empty() {
+            write!(f, " except [{}]", exemptions)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Client {
+    pub fn project_ref_restrictions(
+        &self,
+        project_key: &str,
+    ) -> Result<Vec<RefRestriction>, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions",
+            project_key
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn project_ref_restriction(
+        &self,
+        project_key: &str,
+        restriction_id: u64,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions/{}",
+            project_key, restriction_id
+        );
+
+        self.get(&url)
+    }
+
+    pub fn create_project_ref_restriction(
+        &self,
+        project_key: &str,
+        restriction: &NewRefRestriction,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions",
+            project_key
+        );
+
+        self.post(&url, Some(restriction))
+    }
+
+    pub fn update_project_ref_restriction(
+        &self,
+        project_key: &str,
+        restriction_id: u64,
+        restriction: &NewRefRestriction,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions",
+            project_key
+        );
+
+        self.replace_ref_restriction(&url, restriction_id, restriction)
+    }
+
+    pub fn delete_project_ref_restriction(
+        &self,
+        project_key: &str,
+        restriction_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions/{}",
+            project_key, restriction_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn set_project_ref_restrictions(
+        &self,
+        project_key: &str,
+        restrictions: Vec<NewRefRestriction>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/restrictions",
+            project_key
+        );
+        let current = self.project_ref_restrictions(project_key)?;
+
+        self.reconcile_ref_restrictions(&url, current, restrictions)
+    }
+
+    pub fn repository_ref_restrictions(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<Vec<RefRestriction>, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions",
+            project_key, repository_slug
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn repository_ref_restriction(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        restriction_id: u64,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions/{}",
+            project_key, repository_slug, restriction_id
+        );
+
+        self.get(&url)
+    }
+
+    pub fn create_repository_ref_restriction(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        restriction: &NewRefRestriction,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions",
+            project_key, repository_slug
+        );
+
+        self.post(&url, Some(restriction))
+    }
+
+    pub fn update_repository_ref_restriction(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        restriction_id: u64,
+        restriction: &NewRefRestriction,
+    ) -> Result<RefRestriction, Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions",
+            project_key, repository_slug
+        );
+
+        self.replace_ref_restriction(&url, restriction_id, restriction)
+    }
+
+    pub fn delete_repository_ref_restriction(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        restriction_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions/{}",
+            project_key, repository_slug, restriction_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn set_repository_ref_restrictions(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        restrictions: Vec<NewRefRestriction>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/branch-permissions/2.0/projects/{}/repos/{}/restrictions",
+            project_key, repository_slug
+        );
+        let current = self.repository_ref_restrictions(project_key, repository_slug)?;
+
+        self.reconcile_ref_restrictions(&url, current, restrictions)
+    }
+
+    // Restrictions cannot be edited in place. Posting one replaces any
+    // restriction of the same type on the same ref, and the one it stands in
+    // for is removed only afterwards, so the ref is never left unprotected.
+    fn replace_ref_restriction(
+        &self,
+        restrictions_url: &str,
+        restriction_id: u64,
+        restriction: &NewRefRestriction,
+    ) -> Result<RefRestriction, Error> {
+        let replacement: RefRestriction = self.post(restrictions_url, Some(restriction))?;
+        if replacement.id != restriction_id {
+            self.delete(&format!("{}/{}", restrictions_url, restriction_id))?;
+        }
+
+        Ok(replacement)
+    }
+
+    // Changed and new restrictions are written before leftovers are removed,
+    // for the same reason.
+    fn reconcile_ref_restrictions(
+        &self,
+        restrictions_url: &str,
+        current: Vec<RefRestriction>,
+        restrictions: Vec<NewRefRestriction>,
+    ) -> Result<(), Error> {
+        let reconciliation = reconcile(
+            current,
+            &restrictions,
+            RefRestriction::applies_to,
+            RefRestriction::satisfies,
+        );
+
+        // A post can land on any restriction of its type and ref, which must
+        // then survive the deletions below.
+        let mut written = HashSet::new();
+        for (existing, restriction) in reconciliation.updates {
+            println!("Updating {}", restriction);
+            let updated =
+                self.replace_ref_restriction(restrictions_url, existing.id, restriction)?;
+            written.insert(updated.id);
+        }
+
+        for restriction in reconciliation.additions {
+            println!("Adding {}", restriction);
+            let created: RefRestriction = self.post(restrictions_url, Some(restriction))?;
+            written.insert(created.id);
+        }
+
+        for restriction in &reconciliation.deletions {
+            if !written.contains(&restriction.id) {
+                println!("Deleting {}", restriction);
+                self.delete(&format!("{}/{}", restrictions_url, restriction.id))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use client;
+use client::Client;
+use failure::Error;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+pub enum BuildState {
+    #[serde(rename = "SUCCESSFUL")]
+    Successful,
+    #[serde(rename = "FAILED")]
+    Failed,
+    #[serde(rename = "INPROGRESS")]
+    InProgress,
+    #[serde(rename = "CANCELLED")]
+    Cancelled,
+    #[serde(rename = "UNKNOWN")]
+    Unknown,
+}
+
+impl fmt::Display for BuildState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuildState::Successful => write!(f, "SUCCESSFUL"),
+            BuildState::Failed => write!(f, "FAILED"),
+            BuildState::InProgress => write!(f, "INPROGRESS"),
+            BuildState::Cancelled => write!(f, "CANCELLED"),
+            BuildState::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStatus {
+    pub state: BuildState,
+    pub key: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub date_added: Option<u64>,
+}
+
+impl fmt::Display for BuildStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.name.as_ref().unwrap_or(&self.key),
+            self.state
+        )
+    }
+}
+
+impl BuildStatus {
+    pub fn new(key: &str, state: BuildState, url: &str) -> BuildStatus {
+        BuildStatus {
+            state,
+            key: key.to_owned(),
+            url: url.to_owned(),
+            name: None,
+            description: None,
+            date_added: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStats {
+    #[serde(default)]
+    pub successful: u64,
+    #[serde(default)]
+    pub in_progress: u64,
+    #[serde(default)]
+    pub failed: u64,
+    #[serde(default)]
+    pub cancelled: u64,
+    #[serde(default)]
+    pub unknown: u64,
+}
+
+impl fmt::Display for BuildStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} successful, {} in progress, {} failed, {} cancelled, {} unknown",
+            self.successful, self.in_progress, self.failed, self.cancelled, self.unknown
+        )
+    }
+}
+
+impl Client {
+    /// Posts to the legacy build status endpoint, which only accepts the
+    /// successful, failed and in progress states. Post cancelled and unknown
+    /// builds with `post_repository_build_status`.
+    pub fn post_build_status(&self, commit_id: &str, status: &BuildStatus) -> Result<(), Error> {
+        match status.state {
+            BuildState::Cancelled | BuildState::Unknown => {
+                bail!("{} builds must be posted to a repository", status.state)
+            }
+            _ => {}
+        }
+
+        let url = format!("rest/build-status/1.0/commits/{}", commit_id);
+
+        self.post(&url, Some(status))
+    }
+
+    pub fn build_statuses(&self, commit_id: &str) -> Result<Vec<BuildStatus>, Error> {
+        let url = format!("rest/build-status/1.0/commits/{}", commit_id);
+
+        self.get_paged(&url)
+    }
+
+    pub fn build_stats(&self, commit_id: &str) -> Result<BuildStats, Error> {
+        let url = format!("rest/build-status/1.0/commits/stats/{}", commit_id);
+
+        self.get(&url)
+    }
+
+    pub fn builds_stats(&self, commit_ids: &[&str]) -> Result<HashMap<String, BuildStats>, Error> {
+        let url = "rest/build-status/1.0/commits/stats";
+
+        self.post(url, Some(&commit_ids))
+    }
+
+    pub fn post_repository_build_status(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        status: &BuildStatus,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/commits/{}/builds",
+            project_key, repository_slug, commit_id
+        );
+
+        self.post(&url, Some(status))
+    }
+
+    pub fn repository_build_status(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        key: &str,
+    ) -> Result<BuildStatus, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/commits/{}/builds?key={}",
+            project_key,
+            repository_slug,
+            commit_id,
+            client::percent_encode(key)
+        );
+
+        self.get(&url)
+    }
+}
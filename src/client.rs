@@ -1,8 +1,10 @@
 use failure::Error;
 use percent_encoding::{utf8_percent_encode, SIMPLE_ENCODE_SET};
 use reqwest;
+use reqwest::{Method, Response};
 use serde::{Deserialize, Serialize};
 use serde_json;
+use std::fmt;
 use url::Url;
 
 pub struct Client {
@@ -11,6 +13,26 @@ pub struct Client {
     auth: Authentication,
 }
 
+/// An entry in the `errors` list Bitbucket returns with 4xx responses.
+#[derive(Clone, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiError {
+    pub context: Option<String>,
+    pub message: String,
+    pub exception_name: Option<String>,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct ApiErrors {
+    errors: Vec<ApiError>,
+}
+
 pub struct Authentication {
     username: String,
     password: String,
@@ -34,9 +56,9 @@ impl Client {
     where
         for<'de> T: Deserialize<'de>,
     {
-        let url = Url::parse(&format!("{}/{}", self.base_url, path))?;
+        let url = self.url(path)?;
 
-        info!("GET {}", url);
+        info!("GET {}", redacted(&url));
         Ok(self
             .client
             .get(url.clone())
@@ -54,7 +76,7 @@ impl Client {
         let mut start = 0;
 
         loop {
-            let mut page: Page<T> = self.get(&format!("{}?limit=1000&start={}", path, start))?;
+            let mut page: Page<T> = self.get(&paged(path, start))?;
             output.append(&mut page.values);
 
             if page.is_last_page {
@@ -67,12 +89,20 @@ impl Client {
         Ok(output)
     }
 
+    /// Fetches a single page of at most `limit` values, starting at `start`.
+    pub fn get_page<T>(&self, path: &str, start: u64, limit: u64) -> Result<Page<T>, Error>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        self.get(&page_url(path, start, limit))
+    }
+
     pub fn get_lines_paged(&self, path: &str) -> Result<Vec<String>, Error> {
         let mut output = Vec::new();
         let mut start = 0;
 
         loop {
-            let page: LinePage = self.get(&format!("{}?limit=1000&start={}", path, start))?;
+            let page: LinePage = self.get(&paged(path, start))?;
             let mut text = page.lines.into_iter().map(|l| l.text).collect();
             output.append(&mut text);
 
@@ -86,56 +116,176 @@ impl Client {
         Ok(output)
     }
 
+    pub fn post<T, R>(&self, path: &str, value: Option<&T>) -> Result<R, Error>
+    where
+        T: Serialize,
+        for<'de> R: Deserialize<'de>,
+    {
+        self.send(Method::POST, path, value)
+    }
+
     pub fn put<T>(&self, path: &str, value: Option<&T>) -> Result<(), Error>
     where
         T: Serialize,
     {
-        let url = Url::parse(&format!("{}{}", self.base_url, path))?;
+        self.execute(Method::PUT, path, value)?;
+
+        Ok(())
+    }
+
+    pub fn delete(&self, path: &str) -> Result<(), Error> {
+        self.execute::<()>(Method::DELETE, path, None)?;
+
+        Ok(())
+    }
+
+    pub fn send<T, R>(&self, method: Method, path: &str, value: Option<&T>) -> Result<R, Error>
+    where
+        T: Serialize,
+        for<'de> R: Deserialize<'de>,
+    {
+        let text = self.execute(method, path, value)?.text()?;
+
+        // Endpoints that respond with 204 No Content are read as `null`,
+        // so callers can ask for `()` or an `Option`.
+        if text.trim().is_empty() {
+            Ok(serde_json::from_str("null")?)
+        } else {
+            Ok(serde_json::from_str(&text)?)
+        }
+    }
+
+    /// Like `send`, but client errors that carry an `errors` body are
+    /// returned as values instead of failing.
+    pub fn try_send<T, R>(
+        &self,
+        method: Method,
+        path: &str,
+        value: Option<&T>,
+    ) -> Result<Result<R, Vec<ApiError>>, Error>
+    where
+        T: Serialize,
+        for<'de> R: Deserialize<'de>,
+    {
+        let url = self.url(path)?;
+        info!("{} {}", method, redacted(&url));
+
+        let mut req = self
+            .client
+            .request(method, url)
+            .basic_auth(self.auth.username.clone(), Some(self.auth.password.clone()));
+        if let Some(body) = value {
+            req = req.json(&body);
+        }
+
+        let mut response = req.send()?;
+        if response.status().is_client_error() {
+            if let Ok(body) = response.json::<ApiErrors>() {
+                return Ok(Err(body.errors));
+            }
+        }
+
+        let text = response.error_for_status()?.text()?;
+        if text.trim().is_empty() {
+            Ok(Ok(serde_json::from_str("null")?))
+        } else {
+            Ok(Ok(serde_json::from_str(&text)?))
+        }
+    }
+
+    fn execute<T>(&self, method: Method, path: &str, value: Option<&T>) -> Result<Response, Error>
+    where
+        T: Serialize,
+    {
+        let url = self.url(path)?;
+
+        // Bodies are not logged as they may carry passwords or secrets.
+        info!("{} {}", method, redacted(&url));
 
-        info!("PUT {} {}", url, serde_json::to_string(&value)?);
         let req = self
             .client
-            .put(url)
+            .request(method, url)
             .basic_auth(self.auth.username.clone(), Some(self.auth.password.clone()));
 
         if let Some(body) = value {
-            req.json(&body).send()?.error_for_status()?;
+            Ok(req.json(&body).send()?.error_for_status()?)
         } else {
-            req.send()?.error_for_status()?;
+            Ok(req.send()?.error_for_status()?)
         }
-
-        Ok(())
     }
 
-    pub fn delete(&self, path: &str) -> Result<(), Error> {
-        let url = Url::parse(&format!("{}{}", self.base_url, path))?;
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        let base = if self.base_url.ends_with('/') {
+            &self.base_url[..self.base_url.len() - 1]
+        } else {
+            &self.base_url
+        };
+        let path = if path.starts_with('/') {
+            &path[1..]
+        } else {
+            path
+        };
 
-        info!("DELETE {}", url);
-        self.client
-            .delete(url)
-            .basic_auth(self.auth.username.clone(), Some(self.auth.password.clone()))
-            .send()?
-            .error_for_status()?;
+        Ok(Url::parse(&format!("{}/{}", base, path))?)
+    }
+}
 
-        Ok(())
+/// Masks query parameters that carry credentials, such as the password
+/// given when creating a user, so the URL can be logged.
+fn redacted(url: &Url) -> Url {
+    let mut redacted = url.clone();
+    if url.query().is_none() {
+        return redacted;
     }
+
+    redacted
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(url.query_pairs().map(|(k, v)| {
+            let sensitive = ["password", "secret", "token"]
+                .iter()
+                .any(|s| k.to_lowercase().contains(s));
+            (k, if sensitive { "***".into() } else { v })
+        }));
+
+    redacted
+}
+
+fn paged(path: &str, start: u64) -> String {
+    page_url(path, start, 1000)
+}
+
+fn page_url(path: &str, start: u64, limit: u64) -> String {
+    let separator = if path.contains('?') { '&' } else { '?' };
+
+    format!("{}{}limit={}&start={}", path, separator, limit, start)
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct Page<T> {
-    size: u64,
-    limit: u64,
-    is_last_page: bool,
-    values: Vec<T>,
-    start: u64,
+pub struct Page<T> {
+    pub size: u64,
+    pub is_last_page: bool,
+    pub values: Vec<T>,
+    /// Where the next page starts, absent on the last page.
+    pub next_page_start: Option<u64>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+        Page {
+            size: self.size,
+            is_last_page: self.is_last_page,
+            values: self.values.into_iter().map(f).collect(),
+            next_page_start: self.next_page_start,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct LinePage {
     lines: Vec<Line>,
-    start: u64,
     size: u64,
     is_last_page: bool,
 }
@@ -0,0 +1,49 @@
+use std::fmt;
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Commit {
+    pub id: String,
+    pub display_id: String,
+    pub author: Person,
+    pub author_timestamp: u64,
+    pub committer: Option<Person>,
+    pub committer_timestamp: Option<u64>,
+    pub message: String,
+    #[serde(default)]
+    pub parents: Vec<CommitRef>,
+}
+
+impl fmt::Display for Commit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.display_id,
+            self.message.lines().next().unwrap_or("")
+        )
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitRef {
+    pub id: String,
+    pub display_id: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub name: String,
+    pub email_address: Option<String>,
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.email_address {
+            Some(ref email_address) => write!(f, "{} <{}>", self.name, email_address),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
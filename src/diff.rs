@@ -0,0 +1,134 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Diff {
+    pub from_hash: Option<String>,
+    pub to_hash: Option<String>,
+    pub context_lines: Option<u64>,
+    pub whitespace: Option<String>,
+    pub diffs: Vec<FileDiff>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub source: Option<DiffPath>,
+    pub destination: Option<DiffPath>,
+    #[serde(default)]
+    pub hunks: Vec<Hunk>,
+    #[serde(default)]
+    pub binary: bool,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl FileDiff {
+    pub fn path(&self) -> Option<PathBuf> {
+        self.destination
+            .as_ref()
+            .or(self.source.as_ref())
+            .map(DiffPath::path)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Hunk {
+    pub source_line: u64,
+    pub source_span: u64,
+    pub destination_line: u64,
+    pub destination_span: u64,
+    pub segments: Vec<Segment>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    #[serde(rename = "type")]
+    pub segment_type: SegmentType,
+    pub lines: Vec<DiffLine>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SegmentType {
+    Added,
+    Removed,
+    Context,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub source: u64,
+    pub destination: u64,
+    pub line: String,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffPath {
+    pub components: Vec<String>,
+}
+
+impl fmt::Display for DiffPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.components.join("/"))
+    }
+}
+
+impl DiffPath {
+    pub fn path(&self) -> PathBuf {
+        self.components.iter().collect()
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub content_id: Option<String>,
+    pub from_content_id: Option<String>,
+    pub path: DiffPath,
+    pub src_path: Option<DiffPath>,
+    #[serde(rename = "type")]
+    pub change_type: ChangeType,
+    pub node_type: NodeType,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.src_path {
+            Some(ref src_path) => write!(f, "{:?} {} -> {}", self.change_type, src_path, self.path),
+            None => write!(f, "{:?} {}", self.change_type, self.path),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeType {
+    Add,
+    Copy,
+    Delete,
+    Modify,
+    Move,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NodeType {
+    Directory,
+    File,
+    Submodule,
+}
@@ -0,0 +1,181 @@
+use client::{ApiError, Client};
+use failure::Error;
+use itertools::Itertools;
+use reqwest::Method;
+use std::fmt;
+use url::form_urlencoded;
+
+#[derive(Clone, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErasedUser {
+    /// The anonymized name that replaces the user's name everywhere.
+    pub new_identifier: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErasureCheck {
+    Erasable,
+    Blocked(Vec<ApiError>),
+    NotFound,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErasureOutcome {
+    /// Reported by dry runs for users that passed validation.
+    Erasable,
+    Erased(ErasedUser),
+    Blocked(Vec<ApiError>),
+    NotFound,
+    /// The check or the erasure itself failed.
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct ErasureResult {
+    pub user_name: String,
+    pub outcome: ErasureOutcome,
+}
+
+impl fmt::Display for ErasureResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.outcome {
+            ErasureOutcome::Erasable => write!(f, "{}: erasable", self.user_name),
+            ErasureOutcome::Erased(ref erased) => {
+                write!(f, "{}: erased as {}", self.user_name, erased.new_identifier)
+            }
+            ErasureOutcome::Blocked(ref errors) => {
+                write!(
+                    f,
+                    "{}: blocked ({})",
+                    self.user_name,
+                    errors.iter().join("; ")
+                )
+            }
+            ErasureOutcome::NotFound => write!(f, "{}: no such user", self.user_name),
+            ErasureOutcome::Failed(ref error) => {
+                write!(f, "{}: failed ({})", self.user_name, error)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ErasureReport {
+    pub dry_run: bool,
+    pub results: Vec<ErasureResult>,
+}
+
+impl fmt::Display for ErasureReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.dry_run {
+            writeln!(f, "Dry run, nothing was erased")?;
+        }
+        for result in &self.results {
+            writeln!(f, "{}", result)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ErasureReport {
+    pub fn blocked(&self) -> impl Iterator<Item = &ErasureResult> {
+        self.results.iter().filter(|r| match r.outcome {
+            ErasureOutcome::Blocked(_) => true,
+            _ => false,
+        })
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &ErasureResult> {
+        self.results.iter().filter(|r| match r.outcome {
+            ErasureOutcome::NotFound | ErasureOutcome::Failed(_) => true,
+            _ => false,
+        })
+    }
+}
+
+fn erasure_url(user_name: &str) -> String {
+    format!(
+        "rest/api/1.0/admin/users/erasure?{}",
+        form_urlencoded::Serializer::new(String::new())
+            .append_pair("name", user_name)
+            .finish()
+    )
+}
+
+impl Client {
+    /// Runs the server's pre-erasure validation. Users that are still active
+    /// are reported as blocked without asking the server.
+    pub fn check_user_erasure(&self, user_name: &str) -> Result<ErasureCheck, Error> {
+        let user = self
+            .search_users_admin(user_name)?
+            .into_iter()
+            .find(|u| u.name() == user_name);
+
+        match user {
+            Some(ref user) if user.active() => {
+                return Ok(ErasureCheck::Blocked(vec![ApiError {
+                    context: Some(user_name.to_owned()),
+                    message: format!("{} must be deactivated before being erased", user_name),
+                    exception_name: None,
+                }]))
+            }
+            Some(_) => {}
+            None => return Ok(ErasureCheck::NotFound),
+        }
+
+        match self.try_send::<(), ErasedUser>(Method::GET, &erasure_url(user_name), None)? {
+            Ok(_) => Ok(ErasureCheck::Erasable),
+            Err(errors) => Ok(ErasureCheck::Blocked(errors)),
+        }
+    }
+
+    pub fn erase_user(&self, user_name: &str) -> Result<ErasedUser, Error> {
+        match self.check_user_erasure(user_name)? {
+            ErasureCheck::Erasable => {}
+            ErasureCheck::Blocked(errors) => {
+                bail!("cannot erase {}: {}", user_name, errors.iter().join("; "))
+            }
+            ErasureCheck::NotFound => bail!("user {} does not exist", user_name),
+        }
+
+        println!("Erasing {}", user_name);
+        self.post::<(), _>(&erasure_url(user_name), None)
+    }
+
+    /// Validates every user before erasing any; with `dry_run` nothing is
+    /// erased. Users that fail validation are reported, not erased, and a
+    /// failure for one user is recorded in the report without stopping the
+    /// rest of the batch.
+    pub fn erase_users(&self, user_names: &[&str], dry_run: bool) -> ErasureReport {
+        let mut results = Vec::new();
+
+        for user_name in user_names {
+            let outcome = match self.check_user_erasure(user_name) {
+                Ok(ErasureCheck::Erasable) => ErasureOutcome::Erasable,
+                Ok(ErasureCheck::Blocked(errors)) => ErasureOutcome::Blocked(errors),
+                Ok(ErasureCheck::NotFound) => ErasureOutcome::NotFound,
+                Err(e) => ErasureOutcome::Failed(e.to_string()),
+            };
+            results.push(ErasureResult {
+                user_name: user_name.to_string(),
+                outcome,
+            });
+        }
+
+        if !dry_run {
+            for result in &mut results {
+                if result.outcome == ErasureOutcome::Erasable {
+                    println!("Erasing {}", result.user_name);
+                    result.outcome = match self.post::<(), _>(&erasure_url(&result.user_name), None)
+                    {
+                        Ok(erased) => ErasureOutcome::Erased(erased),
+                        Err(e) => ErasureOutcome::Failed(e.to_string()),
+                    };
+                }
+            }
+        }
+
+        ErasureReport { dry_run, results }
+    }
+}
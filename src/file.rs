@@ -20,29 +20,17 @@ impl Client {
         repository_filter: impl Fn(&Repository) -> bool + Sync + Send,
         file_filter: impl Fn(&PathBuf) -> bool + Sync + Send,
     ) -> Result<Vec<FileLocation>, Error> {
-        let projects = self.projects()?.into_par_iter().filter(project_filter);
-
-        let repositories =
-            projects.flat_map(move |project| match self.repositories(&project.key()) {
-                Ok(repositories) => repositories
-                    .into_par_iter()
-                    .filter(|r| repository_filter(&r))
-                    .map(|r| Ok((project.clone(), r)))
-                    .collect(),
-                Err(e) => vec![Err(e)],
-            });
-
-        repositories
+        self.matching_repositories(project_filter, repository_filter)?
             .flat_map(|repository| match repository {
-                Ok(repository) => {
-                    match self.repository_files(&repository.0.key(), &repository.1.slug()) {
+                Ok((project, repository)) => {
+                    match self.repository_files(project.key(), repository.slug()) {
                         Ok(locations) => locations
                             .into_par_iter()
-                            .filter(|f| file_filter(&f))
+                            .filter(|f| file_filter(f))
                             .map(|path| {
                                 Ok(FileLocation {
-                                    project: repository.0.clone(),
-                                    repository: repository.1.clone(),
+                                    project: project.clone(),
+                                    repository: repository.clone(),
                                     path,
                                 })
                             }).collect(),
@@ -1,11 +1,17 @@
-use client::Client;
+use client::{Client, Page};
 use failure::Error;
+use reqwest::Method;
+use std::collections::HashSet;
 use std::fmt;
+use url::form_urlencoded;
+use user::User;
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Group {
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deletable: Option<bool>,
 }
 
 impl fmt::Display for Group {
@@ -18,18 +24,215 @@ impl Group {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Only reported by the admin endpoints.
+    pub fn deletable(&self) -> Option<bool> {
+        self.deletable
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GroupUsers<'a> {
+    group: &'a str,
+    users: &'a [&'a str],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct UserGroups<'a> {
+    user: &'a str,
+    groups: &'a [&'a str],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Membership<'a> {
+    context: &'a str,
+    item_name: &'a str,
+}
+
+fn query(pairs: &[(&str, &str)]) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish()
+}
+
+fn group_named(name: String) -> Group {
+    Group {
+        name,
+        deletable: None,
+    }
+}
+
+fn filtered(path: &str, filter: Option<&str>) -> String {
+    match filter {
+        Some(filter) => format!("{}?{}", path, query(&[("filter", filter)])),
+        None => path.to_owned(),
+    }
 }
 
 impl Client {
-    pub fn groups_admin(&self) -> Result<Vec<Group>, Error> {
-        let url = "rest/api/1.0/admin/groups";
+    pub fn groups_admin(&self, filter: Option<&str>) -> Result<Vec<Group>, Error> {
+        let url = filtered("rest/api/1.0/admin/groups", filter);
 
         self.get_paged(&url)
     }
 
-    pub fn groups(&self) -> Result<Vec<String>, Error> {
-        let url = "rest/api/1.0/groups";
+    /// Fetches one page of groups, for servers with too many to list at once.
+    pub fn groups_admin_page(
+        &self,
+        filter: Option<&str>,
+        start: u64,
+        limit: u64,
+    ) -> Result<Page<Group>, Error> {
+        let url = filtered("rest/api/1.0/admin/groups", filter);
+
+        self.get_page(&url, start, limit)
+    }
+
+    pub fn groups(&self, filter: Option<&str>) -> Result<Vec<Group>, Error> {
+        let url = filtered("rest/api/1.0/groups", filter);
+
+        Ok(self
+            .get_paged::<String>(&url)?
+            .into_iter()
+            .map(group_named)
+            .collect())
+    }
+
+    /// Fetches one page of group names, for servers with too many to list at
+    /// once.
+    pub fn groups_page(
+        &self,
+        filter: Option<&str>,
+        start: u64,
+        limit: u64,
+    ) -> Result<Page<Group>, Error> {
+        let url = filtered("rest/api/1.0/groups", filter);
+
+        Ok(self
+            .get_page::<String>(&url, start, limit)?
+            .map(group_named))
+    }
+
+    pub fn create_group(&self, group_name: &str) -> Result<Group, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/groups?{}",
+            query(&[("name", group_name)])
+        );
+
+        self.post::<(), _>(&url, None)
+    }
+
+    pub fn delete_group(&self, group_name: &str) -> Result<Group, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/groups?{}",
+            query(&[("name", group_name)])
+        );
+
+        self.send::<(), _>(Method::DELETE, &url, None)
+    }
+
+    pub fn group_members(&self, group_name: &str) -> Result<Vec<User>, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/groups/more-members?{}",
+            query(&[("context", group_name)])
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn group_non_members(&self, group_name: &str) -> Result<Vec<User>, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/groups/more-non-members?{}",
+            query(&[("context", group_name)])
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn user_groups(&self, user_name: &str) -> Result<Vec<Group>, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/users/more-members?{}",
+            query(&[("context", user_name)])
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn user_non_member_groups(&self, user_name: &str) -> Result<Vec<Group>, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/users/more-non-members?{}",
+            query(&[("context", user_name)])
+        );
 
         self.get_paged(&url)
     }
+
+    pub fn add_users_to_group(&self, group_name: &str, user_names: &[&str]) -> Result<(), Error> {
+        let url = "rest/api/1.0/admin/groups/add-users";
+        let body = GroupUsers {
+            group: group_name,
+            users: user_names,
+        };
+
+        self.post(url, Some(&body))
+    }
+
+    pub fn add_user_to_groups(&self, user_name: &str, group_names: &[&str]) -> Result<(), Error> {
+        let url = "rest/api/1.0/admin/users/add-groups";
+        let body = UserGroups {
+            user: user_name,
+            groups: group_names,
+        };
+
+        self.post(url, Some(&body))
+    }
+
+    pub fn remove_user_from_group(&self, group_name: &str, user_name: &str) -> Result<(), Error> {
+        let url = "rest/api/1.0/admin/groups/remove-user";
+        let body = Membership {
+            context: group_name,
+            item_name: user_name,
+        };
+
+        self.post(url, Some(&body))
+    }
+
+    pub fn remove_users_from_group(
+        &self,
+        group_name: &str,
+        user_names: &[&str],
+    ) -> Result<(), Error> {
+        for user_name in user_names {
+            self.remove_user_from_group(group_name, user_name)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_group_members(&self, group_name: &str, user_names: &[&str]) -> Result<(), Error> {
+        let members = self.group_members(group_name)?;
+        let current: HashSet<&str> = members.iter().map(User::name).collect();
+        let desired: HashSet<&str> = user_names.iter().cloned().collect();
+
+        let mut removed: Vec<&str> = current.difference(&desired).cloned().collect();
+        removed.sort();
+        for user_name in &removed {
+            println!("Deleting {} from {}", user_name, group_name);
+        }
+        self.remove_users_from_group(group_name, &removed)?;
+
+        let mut added: Vec<&str> = desired.difference(&current).cloned().collect();
+        added.sort();
+        if !added.is_empty() {
+            for user_name in &added {
+                println!("Adding {} to {}", user_name, group_name);
+            }
+            self.add_users_to_group(group_name, &added)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,408 @@
+use client::Client;
+use failure::Error;
+use merging::{RequiredApproversSettings, RequiredBuildsSettings};
+use project::Project;
+use rayon::prelude::*;
+use repository::Repository;
+use reqwest::Method;
+use serde::de::{Deserialize, DeserializeOwned};
+use serde::ser::Serialize;
+use std::fmt;
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HookType {
+    PreReceive,
+    PostReceive,
+    PrePullRequestMerge,
+}
+
+impl fmt::Display for HookType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HookType::PreReceive => write!(f, "PRE_RECEIVE"),
+            HookType::PostReceive => write!(f, "POST_RECEIVE"),
+            HookType::PrePullRequestMerge => write!(f, "PRE_PULL_REQUEST_MERGE"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HookScopeType {
+    Project,
+    Repository,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HookScope {
+    #[serde(rename = "type")]
+    pub scope_type: HookScopeType,
+    pub resource_id: u64,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HookDetails {
+    key: String,
+    name: String,
+    #[serde(rename = "type")]
+    hook_type: HookType,
+    description: Option<String>,
+    version: Option<String>,
+    config_form_key: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryHook {
+    details: HookDetails,
+    enabled: bool,
+    configured: bool,
+    scope: Option<HookScope>,
+}
+
+impl fmt::Display for RepositoryHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.details.name, self.details.key)
+    }
+}
+
+impl RepositoryHook {
+    pub fn key(&self) -> &str {
+        &self.details.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.details.name
+    }
+
+    pub fn hook_type(&self) -> HookType {
+        self.details.hook_type
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.details.description.as_ref().map(String::as_str)
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.details.version.as_ref().map(String::as_str)
+    }
+
+    pub fn is_configurable(&self) -> bool {
+        self.details.config_form_key.is_some()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn configured(&self) -> bool {
+        self.configured
+    }
+
+    pub fn scope(&self) -> Option<&HookScope> {
+        self.scope.as_ref()
+    }
+
+    /// Whether the hook state on a repository comes from its project.
+    pub fn is_inherited(&self) -> bool {
+        self.scope
+            .as_ref()
+            .map_or(false, |s| s.scope_type == HookScopeType::Project)
+    }
+}
+
+/// Hooks bundled with Bitbucket Server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BuiltinHook {
+    RejectForcePush,
+    VerifyCommitter,
+    RequiredApprovers,
+    RequiredBuilds,
+}
+
+impl BuiltinHook {
+    pub fn key(self) -> &'static str {
+        match self {
+            BuiltinHook::RejectForcePush => {
+                "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:force-push-hook"
+            }
+            BuiltinHook::VerifyCommitter => {
+                "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:verify-committer-hook"
+            }
+            BuiltinHook::RequiredApprovers => {
+                "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:requiredApproversMergeHook"
+            }
+            BuiltinHook::RequiredBuilds => {
+                "com.atlassian.bitbucket.server.bitbucket-build:requiredBuildsMergeCheck"
+            }
+        }
+    }
+
+    pub fn hook_type(self) -> HookType {
+        match self {
+            BuiltinHook::RejectForcePush | BuiltinHook::VerifyCommitter => HookType::PreReceive,
+            BuiltinHook::RequiredApprovers | BuiltinHook::RequiredBuilds => {
+                HookType::PrePullRequestMerge
+            }
+        }
+    }
+}
+
+impl fmt::Display for BuiltinHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuiltinHook::RejectForcePush => write!(f, "Reject Force Push"),
+            BuiltinHook::VerifyCommitter => write!(f, "Verify Committer"),
+            BuiltinHook::RequiredApprovers => write!(f, "Minimum Approvals"),
+            BuiltinHook::RequiredBuilds => write!(f, "Minimum Successful Builds"),
+        }
+    }
+}
+
+/// Typed settings for a configurable built-in hook. Reject Force Push and
+/// Verify Committer take no settings.
+pub trait BuiltinHookSettings: Serialize + DeserializeOwned {
+    const HOOK: BuiltinHook;
+}
+
+impl BuiltinHookSettings for RequiredApproversSettings {
+    const HOOK: BuiltinHook = BuiltinHook::RequiredApprovers;
+}
+
+impl BuiltinHookSettings for RequiredBuildsSettings {
+    const HOOK: BuiltinHook = BuiltinHook::RequiredBuilds;
+}
+
+fn hooks_url(base: &str, hook_type: Option<HookType>) -> String {
+    match hook_type {
+        Some(hook_type) => format!("{}/settings/hooks?type={}", base, hook_type),
+        None => format!("{}/settings/hooks", base),
+    }
+}
+
+impl Client {
+    pub fn project_hooks(
+        &self,
+        project_key: &str,
+        hook_type: Option<HookType>,
+    ) -> Result<Vec<RepositoryHook>, Error> {
+        let base = format!("rest/api/1.0/projects/{}", project_key);
+
+        self.get_paged(&hooks_url(&base, hook_type))
+    }
+
+    pub fn repository_hooks(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_type: Option<HookType>,
+    ) -> Result<Vec<RepositoryHook>, Error> {
+        let base = format!(
+            "rest/api/1.0/projects/{}/repos/{}",
+            project_key, repository_slug
+        );
+
+        self.get_paged(&hooks_url(&base, hook_type))
+    }
+
+    pub fn project_hook(&self, project_key: &str, hook_key: &str) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}",
+            project_key, hook_key
+        );
+
+        self.get(&url)
+    }
+
+    pub fn repository_hook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_key: &str,
+    ) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/hooks/{}",
+            project_key, repository_slug, hook_key
+        );
+
+        self.get(&url)
+    }
+
+    pub fn enable_project_hook(
+        &self,
+        project_key: &str,
+        hook_key: &str,
+    ) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/enabled",
+            project_key, hook_key
+        );
+
+        self.send::<(), _>(Method::PUT, &url, None)
+    }
+
+    pub fn disable_project_hook(
+        &self,
+        project_key: &str,
+        hook_key: &str,
+    ) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/enabled",
+            project_key, hook_key
+        );
+
+        self.send::<(), _>(Method::DELETE, &url, None)
+    }
+
+    pub fn enable_repository_hook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_key: &str,
+    ) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/hooks/{}/enabled",
+            project_key, repository_slug, hook_key
+        );
+
+        self.send::<(), _>(Method::PUT, &url, None)
+    }
+
+    pub fn disable_repository_hook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_key: &str,
+    ) -> Result<RepositoryHook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/hooks/{}/enabled",
+            project_key, repository_slug, hook_key
+        );
+
+        self.send::<(), _>(Method::DELETE, &url, None)
+    }
+
+    /// Returns `None` when the hook has never been configured.
+    pub fn project_hook_settings<T>(
+        &self,
+        project_key: &str,
+        hook_key: &str,
+    ) -> Result<Option<T>, Error>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/settings",
+            project_key, hook_key
+        );
+
+        self.send::<(), _>(Method::GET, &url, None)
+    }
+
+    /// Returns `None` when the hook has never been configured.
+    pub fn repository_hook_settings<T>(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_key: &str,
+    ) -> Result<Option<T>, Error>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/hooks/{}/settings",
+            project_key, repository_slug, hook_key
+        );
+
+        self.send::<(), _>(Method::GET, &url, None)
+    }
+
+    pub fn set_project_hook_settings<T: Serialize>(
+        &self,
+        project_key: &str,
+        hook_key: &str,
+        settings: &T,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/settings",
+            project_key, hook_key
+        );
+
+        self.put(&url, Some(settings))
+    }
+
+    pub fn set_repository_hook_settings<T: Serialize>(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook_key: &str,
+        settings: &T,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/hooks/{}/settings",
+            project_key, repository_slug, hook_key
+        );
+
+        self.put(&url, Some(settings))
+    }
+
+    pub fn enable_builtin_hook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        hook: BuiltinHook,
+    ) -> Result<RepositoryHook, Error> {
+        self.enable_repository_hook(project_key, repository_slug, hook.key())
+    }
+
+    /// Returns `None` when the hook has never been configured.
+    pub fn builtin_hook_settings<T: BuiltinHookSettings>(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<Option<T>, Error> {
+        self.repository_hook_settings(project_key, repository_slug, T::HOOK.key())
+    }
+
+    pub fn set_builtin_hook_settings<T: BuiltinHookSettings>(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        settings: &T,
+    ) -> Result<(), Error> {
+        self.set_repository_hook_settings(project_key, repository_slug, T::HOOK.key(), settings)
+    }
+
+    /// Enables `hooks` on every matching repository where they are not
+    /// already enabled, returning the repositories that were checked.
+    pub fn enforce_hooks(
+        &self,
+        project_filter: impl Fn(&Project) -> bool + Sync + Send,
+        repository_filter: impl Fn(&Repository) -> bool + Sync + Send,
+        hooks: &[BuiltinHook],
+    ) -> Result<Vec<Repository>, Error> {
+        self.matching_repositories(project_filter, repository_filter)?
+            .map(|repository| {
+                let (_, repository) = repository?;
+                let current =
+                    self.repository_hooks(&repository.project.key, repository.slug(), None)?;
+
+                for hook in hooks {
+                    if !current.iter().any(|h| h.key() == hook.key() && h.enabled) {
+                        println!("Enabling {} on {}", hook, repository);
+                        self.enable_builtin_hook(
+                            &repository.project.key,
+                            repository.slug(),
+                            *hook,
+                        )?;
+                    }
+                }
+
+                Ok(repository)
+            })
+            .collect()
+    }
+}
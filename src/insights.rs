@@ -0,0 +1,235 @@
+use client::Client;
+use failure::Error;
+use reqwest::Method;
+use serde_json::Value;
+use std::fmt;
+
+pub const MAX_ANNOTATIONS_PER_REQUEST: usize = 1000;
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReportResult {
+    Pass,
+    Fail,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DataType {
+    Boolean,
+    Date,
+    Duration,
+    Link,
+    Number,
+    Percentage,
+    Text,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportData {
+    pub title: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<DataType>,
+    pub value: Value,
+}
+
+impl ReportData {
+    pub fn new(title: &str, data_type: DataType, value: Value) -> ReportData {
+        ReportData {
+            title: title.to_owned(),
+            data_type: Some(data_type),
+            value,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InsightReport {
+    #[serde(skip_serializing)]
+    pub key: Option<String>,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ReportResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<ReportData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    #[serde(skip_serializing)]
+    pub created_date: Option<u64>,
+}
+
+impl fmt::Display for InsightReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.result {
+            Some(result) => write!(f, "{} ({:?})", self.title, result),
+            None => write!(f, "{}", self.title),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnnotationSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnnotationType {
+    Vulnerability,
+    CodeSmell,
+    Bug,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub line: u64,
+    pub message: String,
+    pub severity: AnnotationSeverity,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub annotation_type: Option<AnnotationType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(path: &str, line: u64, message: &str, severity: AnnotationSeverity) -> Annotation {
+        Annotation {
+            external_id: None,
+            path: Some(path.to_owned()),
+            line,
+            message: message.to_owned(),
+            severity,
+            annotation_type: None,
+            link: None,
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.path {
+            Some(ref path) => write!(f, "{}:{} {}", path, self.line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct Annotations<T> {
+    annotations: T,
+}
+
+impl Client {
+    pub fn insight_report(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+    ) -> Result<InsightReport, Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        self.get(&url)
+    }
+
+    pub fn set_insight_report(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+        report: &InsightReport,
+    ) -> Result<InsightReport, Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        self.send(Method::PUT, &url, Some(report))
+    }
+
+    pub fn delete_insight_report(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn insight_annotations(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+    ) -> Result<Vec<Annotation>, Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}/annotations",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        Ok(self.get::<Annotations<Vec<Annotation>>>(&url)?.annotations)
+    }
+
+    pub fn add_insight_annotations(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+        annotations: &[Annotation],
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}/annotations",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        for chunk in annotations.chunks(MAX_ANNOTATIONS_PER_REQUEST) {
+            self.post::<_, ()>(&url, Some(&Annotations { annotations: chunk }))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn delete_insight_annotations(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        commit_id: &str,
+        report_key: &str,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/insights/1.0/projects/{}/repos/{}/commits/{}/reports/{}/annotations",
+            project_key, repository_slug, commit_id, report_key
+        );
+
+        self.delete(&url)
+    }
+}
@@ -1,5 +1,7 @@
+extern crate base64;
 #[macro_use]
 extern crate failure;
+extern crate hmac;
 extern crate itertools;
 #[macro_use]
 extern crate log;
@@ -11,13 +13,29 @@ extern crate serde;
 extern crate serde_derive;
 extern crate rayon;
 extern crate serde_json;
+extern crate sha2;
 extern crate url;
 
 pub mod auth;
+pub mod branch_model;
+pub mod branch_permission;
+pub mod build_status;
 pub mod client;
+pub mod commit;
+pub mod diff;
+pub mod erasure;
 pub mod file;
 pub mod group;
+pub mod hook;
+pub mod insights;
+pub mod license;
 pub mod merging;
 pub mod project;
+pub mod pull_request;
+pub mod ref_matcher;
 pub mod repository;
+pub mod required_builds;
 pub mod user;
+pub mod webhook;
+
+mod reconcile;
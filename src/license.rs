@@ -0,0 +1,126 @@
+use auth::access::UserAccess;
+use auth::permission::GlobalPermission;
+use auth::token::{now_millis, MILLIS_PER_DAY};
+use client::Client;
+use failure::Error;
+use std::collections::HashSet;
+use std::fmt;
+use user::{PermissionFilter, User, UserSearch};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseStatus {
+    pub current_number_of_users: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct License {
+    pub creation_date: Option<u64>,
+    pub purchase_date: Option<u64>,
+    pub expiry_date: Option<u64>,
+    pub maintenance_expiry_date: Option<u64>,
+    pub maximum_number_of_users: Option<u64>,
+    #[serde(default)]
+    pub unlimited_number_of_users: bool,
+    pub server_id: Option<String>,
+    pub support_entitlement_number: Option<String>,
+    pub status: LicenseStatus,
+}
+
+impl License {
+    /// `None` for unlimited licenses.
+    pub fn user_limit(&self) -> Option<u64> {
+        if self.unlimited_number_of_users {
+            None
+        } else {
+            self.maximum_number_of_users
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SeatReport {
+    pub licensed_users: u64,
+    /// `None` for unlimited licenses.
+    pub user_limit: Option<u64>,
+    /// Active licensed users who have not authenticated within the window,
+    /// including those who never have.
+    pub inactive_users: Vec<User>,
+    /// Global permissions granted directly to deactivated users.
+    pub deactivated_grants: Vec<UserAccess<GlobalPermission>>,
+}
+
+impl fmt::Display for SeatReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.user_limit {
+            Some(limit) => write!(f, "{}/{} seats", self.licensed_users, limit)?,
+            None => write!(f, "{} seats (unlimited)", self.licensed_users)?,
+        }
+        write!(
+            f,
+            ", {} inactive, {} deactivated grants",
+            self.inactive_users.len(),
+            self.deactivated_grants.len()
+        )
+    }
+}
+
+impl SeatReport {
+    /// `None` for unlimited licenses.
+    pub fn available_seats(&self) -> Option<i64> {
+        self.user_limit
+            .map(|limit| limit as i64 - self.licensed_users as i64)
+    }
+
+    /// Seats that would be free if every inactive user were removed.
+    pub fn reclaimable_seats(&self) -> u64 {
+        self.inactive_users.len() as u64
+    }
+}
+
+impl Client {
+    pub fn license(&self) -> Result<License, Error> {
+        let url = "rest/api/1.0/admin/license";
+
+        self.get(url)
+    }
+
+    pub fn seat_report(&self, inactive_days: u64) -> Result<SeatReport, Error> {
+        let license = self.license()?;
+        let cutoff = now_millis()?.saturating_sub(inactive_days.saturating_mul(MILLIS_PER_DAY));
+
+        let search = UserSearch {
+            permissions: vec![PermissionFilter::Global(GlobalPermission::LicensedUser)],
+            ..UserSearch::default()
+        };
+        let licensed: HashSet<String> = self
+            .search_users(&search)?
+            .into_iter()
+            .map(|u| u.slug().to_owned())
+            .collect();
+
+        let inactive_users = self
+            .users_admin()?
+            .into_iter()
+            .filter(|u| u.active() && licensed.contains(u.slug()))
+            .filter(|u| {
+                u.last_authentication_timestamp()
+                    .map_or(true, |last| last < cutoff)
+            })
+            .collect();
+
+        let deactivated_grants = self
+            .user_access()?
+            .into_iter()
+            .filter(|a| !a.user().active())
+            .collect();
+
+        Ok(SeatReport {
+            licensed_users: license.status.current_number_of_users,
+            user_limit: license.user_limit(),
+            inactive_users,
+            deactivated_grants,
+        })
+    }
+}
@@ -19,18 +19,28 @@
     - require n successful builds
 */
 
+use branch_model::BranchModel;
 use client::Client;
 use failure::Error;
 use itertools::Itertools;
+use project::Project;
+use rayon::iter::ParallelIterator;
+use reconcile::reconcile;
+use ref_matcher::{qualify, RefMatcher};
 use repository::Repository;
+use reqwest::Method;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::collections::HashSet;
 use std::fmt;
+use url::form_urlencoded;
 use user::User;
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DefaultReviewerGroup {
     id: u64,
-    repository: Repository,
+    repository: Option<Repository>,
     source_ref_matcher: RefMatcher,
     target_ref_matcher: RefMatcher,
     reviewers: Vec<User>,
@@ -43,6 +53,96 @@ impl fmt::Display for DefaultReviewerGroup {
     }
 }
 
+impl DefaultReviewerGroup {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn repository(&self) -> Option<&Repository> {
+        self.repository.as_ref()
+    }
+
+    pub fn source_ref_matcher(&self) -> &RefMatcher {
+        &self.source_ref_matcher
+    }
+
+    pub fn target_ref_matcher(&self) -> &RefMatcher {
+        &self.target_ref_matcher
+    }
+
+    pub fn reviewers(&self) -> &[User] {
+        &self.reviewers
+    }
+
+    pub fn required_approvals(&self) -> u64 {
+        self.required_approvals
+    }
+
+    fn applies_to(&self, condition: &DefaultReviewerCondition) -> bool {
+        self.source_ref_matcher.same_ref(&condition.source_matcher)
+            && self.target_ref_matcher.same_ref(&condition.target_matcher)
+    }
+
+    fn satisfies(&self, condition: &DefaultReviewerCondition) -> bool {
+        let reviewers: HashSet<&str> = self.reviewers.iter().map(User::name).collect();
+        let desired: HashSet<&str> = condition.reviewers.iter().map(User::name).collect();
+
+        self.applies_to(condition)
+            && reviewers == desired
+            && self.required_approvals == condition.required_approvals
+    }
+}
+
+#[derive(Debug)]
+pub struct ReviewerPreview {
+    pub reviewers: Vec<User>,
+    pub conditions: Vec<DefaultReviewerGroup>,
+}
+
+impl fmt::Display for ReviewerPreview {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] ({} approvals required)",
+            self.reviewers.iter().join(", "),
+            self.required_approvals()
+        )
+    }
+}
+
+impl ReviewerPreview {
+    /// The largest number of approvals required by any of the matched
+    /// conditions.
+    pub fn required_approvals(&self) -> u64 {
+        self.conditions
+            .iter()
+            .map(DefaultReviewerGroup::required_approvals)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultReviewerCondition {
+    pub source_matcher: RefMatcher,
+    pub target_matcher: RefMatcher,
+    pub reviewers: Vec<User>,
+    pub required_approvals: u64,
+}
+
+impl fmt::Display for DefaultReviewerCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}: [{}]",
+            self.source_matcher,
+            self.target_matcher,
+            self.reviewers.iter().join(", ")
+        )
+    }
+}
+
 impl Client {
     pub fn default_reviewers(
         &self,
@@ -56,21 +156,752 @@ impl Client {
 
         self.get(&url)
     }
+
+    pub fn default_reviewers_preview(
+        &self,
+        source: &Repository,
+        source_ref: &str,
+        target: &Repository,
+        target_ref: &str,
+    ) -> Result<ReviewerPreview, Error> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .append_pair("sourceRepoId", &source.id.to_string())
+            .append_pair("sourceRefId", &qualify(source_ref))
+            .append_pair("targetRepoId", &target.id.to_string())
+            .append_pair("targetRefId", &qualify(target_ref))
+            .finish();
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/repos/{}/reviewers?{}",
+            target.project.key,
+            target.slug(),
+            query
+        );
+        let reviewers = self.get(&url)?;
+
+        // Each ref is classified by the branching model of its own repository,
+        // which is only fetched when a condition needs it.
+        let groups = self.default_reviewers(&target.project.key, target.slug())?;
+        let source_uses_model = groups
+            .iter()
+            .any(|g| g.source_ref_matcher.uses_branch_model());
+        let target_uses_model = groups
+            .iter()
+            .any(|g| g.target_ref_matcher.uses_branch_model());
+        let source_model = self.branch_model_for(source, source_uses_model)?;
+        let target_model = self.branch_model_for(target, target_uses_model)?;
+        let conditions = groups
+            .into_iter()
+            .filter(|g| {
+                g.source_ref_matcher
+                    .matches(source_ref, source_model.as_ref())
+                    && g.target_ref_matcher
+                        .matches(target_ref, target_model.as_ref())
+            })
+            .collect();
+
+        Ok(ReviewerPreview {
+            reviewers,
+            conditions,
+        })
+    }
+
+    fn branch_model_for(
+        &self,
+        repository: &Repository,
+        needed: bool,
+    ) -> Result<Option<BranchModel>, Error> {
+        if !needed {
+            return Ok(None);
+        }
+
+        Ok(Some(self.repository_branch_model(
+            &repository.project.key,
+            repository.slug(),
+        )?))
+    }
+
+    pub fn create_default_reviewers(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        condition: &DefaultReviewerCondition,
+    ) -> Result<DefaultReviewerGroup, Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/repos/{}/condition",
+            project_key, repository_slug
+        );
+
+        self.post(&url, Some(condition))
+    }
+
+    pub fn update_default_reviewers(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        condition_id: u64,
+        condition: &DefaultReviewerCondition,
+    ) -> Result<DefaultReviewerGroup, Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/repos/{}/condition/{}",
+            project_key, repository_slug, condition_id
+        );
+
+        self.send(Method::PUT, &url, Some(condition))
+    }
+
+    pub fn delete_default_reviewers(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        condition_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/repos/{}/condition/{}",
+            project_key, repository_slug, condition_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn set_default_reviewers(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        conditions: Vec<DefaultReviewerCondition>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/repos/{}",
+            project_key, repository_slug
+        );
+        // The repository listing includes the conditions it inherits from its
+        // project, which can only be changed on the project.
+        let current = self
+            .default_reviewers(project_key, repository_slug)?
+            .into_iter()
+            .filter(|group| group.repository.is_some())
+            .collect();
+
+        self.reconcile_default_reviewers(&url, current, conditions)
+    }
+
+    pub fn project_default_reviewers(
+        &self,
+        project_key: &str,
+    ) -> Result<Vec<DefaultReviewerGroup>, Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/conditions",
+            project_key
+        );
+
+        self.get(&url)
+    }
+
+    pub fn create_project_default_reviewers(
+        &self,
+        project_key: &str,
+        condition: &DefaultReviewerCondition,
+    ) -> Result<DefaultReviewerGroup, Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/condition",
+            project_key
+        );
+
+        self.post(&url, Some(condition))
+    }
+
+    pub fn update_project_default_reviewers(
+        &self,
+        project_key: &str,
+        condition_id: u64,
+        condition: &DefaultReviewerCondition,
+    ) -> Result<DefaultReviewerGroup, Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/condition/{}",
+            project_key, condition_id
+        );
+
+        self.send(Method::PUT, &url, Some(condition))
+    }
+
+    pub fn delete_project_default_reviewers(
+        &self,
+        project_key: &str,
+        condition_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/default-reviewers/1.0/projects/{}/condition/{}",
+            project_key, condition_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn set_project_default_reviewers(
+        &self,
+        project_key: &str,
+        conditions: Vec<DefaultReviewerCondition>,
+    ) -> Result<(), Error> {
+        let url = format!("rest/default-reviewers/1.0/projects/{}", project_key);
+        let current = self.project_default_reviewers(project_key)?;
+
+        self.reconcile_default_reviewers(&url, current, conditions)
+    }
+
+    fn reconcile_default_reviewers(
+        &self,
+        scope_url: &str,
+        current: Vec<DefaultReviewerGroup>,
+        conditions: Vec<DefaultReviewerCondition>,
+    ) -> Result<(), Error> {
+        let reconciliation = reconcile(
+            current,
+            &conditions,
+            DefaultReviewerGroup::applies_to,
+            DefaultReviewerGroup::satisfies,
+        );
+
+        for group in &reconciliation.deletions {
+            println!("Deleting {}", group);
+            self.delete(&format!("{}/condition/{}", scope_url, group.id))?;
+        }
+
+        for (group, condition) in reconciliation.updates {
+            println!("Updating {}", condition);
+            let url = format!("{}/condition/{}", scope_url, group.id);
+            self.send::<_, DefaultReviewerGroup>(Method::PUT, &url, Some(condition))?;
+        }
+
+        for condition in reconciliation.additions {
+            println!("Adding {}", condition);
+            let url = format!("{}/condition", scope_url);
+            self.post::<_, DefaultReviewerGroup>(&url, Some(condition))?;
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeStrategyId {
+    NoFf,
+    Ff,
+    FfOnly,
+    Squash,
+    SquashFfOnly,
+    RebaseNoFf,
+    RebaseFfOnly,
+}
+
+impl fmt::Display for MergeStrategyId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MergeStrategyId::NoFf => write!(f, "no-ff"),
+            MergeStrategyId::Ff => write!(f, "ff"),
+            MergeStrategyId::FfOnly => write!(f, "ff-only"),
+            MergeStrategyId::Squash => write!(f, "squash"),
+            MergeStrategyId::SquashFfOnly => write!(f, "squash-ff-only"),
+            MergeStrategyId::RebaseNoFf => write!(f, "rebase-no-ff"),
+            MergeStrategyId::RebaseFfOnly => write!(f, "rebase-ff-only"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeStrategy {
+    id: MergeStrategyId,
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    enabled: bool,
+}
+
+impl fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(f, "{}", name),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl MergeStrategy {
+    pub fn id(&self) -> MergeStrategyId {
+        self.id
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(String::as_str)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(String::as_str)
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MergeConfigType {
+    Default,
+    Project,
+    Repository,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
-pub struct RefMatcher {
-    active: bool,
-    id: String,
-    display_id: String,
+pub struct MergeConfig {
+    default_strategy: Option<MergeStrategy>,
+    #[serde(default)]
+    strategies: Vec<MergeStrategy>,
     #[serde(rename = "type")]
-    _type: MatcherType,
+    _type: MergeConfigType,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+impl fmt::Display for MergeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.enabled_strategies().iter().join(", "))?;
+        if let Some(default_strategy) = self.default_strategy() {
+            write!(f, " (default {})", default_strategy)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MergeConfig {
+    pub fn default_strategy(&self) -> Option<MergeStrategyId> {
+        self.default_strategy.as_ref().map(MergeStrategy::id)
+    }
+
+    pub fn strategies(&self) -> &[MergeStrategy] {
+        &self.strategies
+    }
+
+    pub fn enabled_strategies(&self) -> Vec<MergeStrategyId> {
+        self.strategies
+            .iter()
+            .filter(|s| s.enabled)
+            .map(MergeStrategy::id)
+            .collect()
+    }
+
+    /// Where the configuration comes from. A repository whose configuration
+    /// is not of type `Repository` inherits it from its project (or the
+    /// server default).
+    pub fn config_type(&self) -> MergeConfigType {
+        self._type
+    }
+
+    pub fn is_inherited_by(&self, scope: MergeConfigType) -> bool {
+        self._type != scope
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MergeSettings {
+    merge_config: MergeConfig,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MergeSettingsUpdate {
+    merge_config: MergeConfigUpdate,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MergeConfigUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_strategy: Option<MergeStrategyRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    strategies: Vec<MergeStrategyRef>,
+}
+
+#[derive(Serialize, Debug)]
+struct MergeStrategyRef {
+    id: MergeStrategyId,
+}
+
+impl MergeSettingsUpdate {
+    fn new(default_strategy: MergeStrategyId, enabled: &[MergeStrategyId]) -> Result<Self, Error> {
+        if !enabled.contains(&default_strategy) {
+            bail!(
+                "default merge strategy {} is not one of the enabled strategies",
+                default_strategy
+            );
+        }
+
+        Ok(MergeSettingsUpdate {
+            merge_config: MergeConfigUpdate {
+                default_strategy: Some(MergeStrategyRef {
+                    id: default_strategy,
+                }),
+                strategies: enabled.iter().map(|&id| MergeStrategyRef { id }).collect(),
+            },
+        })
+    }
+
+    fn inherit() -> Self {
+        MergeSettingsUpdate {
+            merge_config: MergeConfigUpdate {
+                default_strategy: None,
+                strategies: Vec::new(),
+            },
+        }
+    }
+}
+
+impl Client {
+    pub fn project_merge_config(&self, project_key: &str) -> Result<MergeConfig, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/pull-requests/git",
+            project_key
+        );
+
+        Ok(self.get::<MergeSettings>(&url)?.merge_config)
+    }
+
+    pub fn set_project_merge_strategies(
+        &self,
+        project_key: &str,
+        default_strategy: MergeStrategyId,
+        enabled: &[MergeStrategyId],
+    ) -> Result<MergeConfig, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/pull-requests/git",
+            project_key
+        );
+        let settings = MergeSettingsUpdate::new(default_strategy, enabled)?;
+
+        Ok(self
+            .post::<_, MergeSettings>(&url, Some(&settings))?
+            .merge_config)
+    }
+
+    pub fn repository_merge_config(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<MergeConfig, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/pull-requests",
+            project_key, repository_slug
+        );
+
+        Ok(self.get::<MergeSettings>(&url)?.merge_config)
+    }
+
+    pub fn set_repository_merge_strategies(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        default_strategy: MergeStrategyId,
+        enabled: &[MergeStrategyId],
+    ) -> Result<MergeConfig, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/pull-requests",
+            project_key, repository_slug
+        );
+        let settings = MergeSettingsUpdate::new(default_strategy, enabled)?;
+
+        Ok(self
+            .post::<_, MergeSettings>(&url, Some(&settings))?
+            .merge_config)
+    }
+
+    pub fn inherit_repository_merge_strategies(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<MergeConfig, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/pull-requests",
+            project_key, repository_slug
+        );
+
+        Ok(self
+            .post::<_, MergeSettings>(&url, Some(&MergeSettingsUpdate::inherit()))?
+            .merge_config)
+    }
+
+    pub fn enforce_project_merge_strategies(
+        &self,
+        project_key: &str,
+        default_strategy: MergeStrategyId,
+        enabled: &[MergeStrategyId],
+    ) -> Result<(), Error> {
+        self.set_project_merge_strategies(project_key, default_strategy, enabled)?;
+
+        for repository in self.repositories(project_key)? {
+            let config = self.repository_merge_config(project_key, repository.slug())?;
+            if !config.is_inherited_by(MergeConfigType::Repository) {
+                println!("Inheriting merge strategies in {}", repository);
+                self.inherit_repository_merge_strategies(project_key, repository.slug())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct MatcherType {
-    id: String,
-    name: String,
+pub struct PullRequestSettings {
+    pub required_approvers: u64,
+    pub required_all_approvers: bool,
+    pub required_all_tasks_complete: bool,
+    pub required_successful_builds: u64,
+}
+
+impl fmt::Display for PullRequestSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} approvers{}, {} successful builds{}",
+            self.required_approvers,
+            if self.required_all_approvers {
+                " (all)"
+            } else {
+                ""
+            },
+            self.required_successful_builds,
+            if self.required_all_tasks_complete {
+                ", all tasks complete"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+const REQUIRED_APPROVERS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:requiredApproversMergeHook";
+const ALL_APPROVERS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:allApproversMergeHook";
+const INCOMPLETE_TASKS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:incomplete-tasks-merge-check";
+const REQUIRED_BUILDS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-build:requiredBuildsMergeCheck";
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredApproversSettings {
+    #[serde(default)]
+    pub enable: bool,
+    pub required_count: HookCount,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredBuildsSettings {
+    pub required_count: HookCount,
+}
+
+/// A count in merge check settings. The settings forms store numbers as
+/// strings, so both forms are read and the string form is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookCount(pub u64);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawHookCount {
+    Number(u64),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for HookCount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match RawHookCount::deserialize(deserializer)? {
+            RawHookCount::Number(count) => Ok(HookCount(count)),
+            RawHookCount::Text(count) => count
+                .trim()
+                .parse()
+                .map(HookCount)
+                .map_err(de::Error::custom),
+        }
+    }
+}
+
+impl Serialize for HookCount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct MergeCheckState {
+    enabled: bool,
+}
+
+impl Client {
+    /// Projects have no pull request settings of their own; the same
+    /// requirements are the project's merge checks, which repositories
+    /// inherit unless they override them.
+    pub fn project_pull_request_settings(
+        &self,
+        project_key: &str,
+    ) -> Result<PullRequestSettings, Error> {
+        let approvers: Option<RequiredApproversSettings> =
+            self.project_merge_check(project_key, REQUIRED_APPROVERS_CHECK)?;
+        let builds: Option<RequiredBuildsSettings> =
+            self.project_merge_check(project_key, REQUIRED_BUILDS_CHECK)?;
+
+        Ok(PullRequestSettings {
+            required_approvers: approvers.map_or(0, |a| a.required_count.0),
+            required_all_approvers: self
+                .project_merge_check_enabled(project_key, ALL_APPROVERS_CHECK)?,
+            required_all_tasks_complete: self
+                .project_merge_check_enabled(project_key, INCOMPLETE_TASKS_CHECK)?,
+            required_successful_builds: builds.map_or(0, |b| b.required_count.0),
+        })
+    }
+
+    /// Enables the project merge checks `settings` asks for and disables the
+    /// rest, returning the settings as read back from the project.
+    pub fn set_project_pull_request_settings(
+        &self,
+        project_key: &str,
+        settings: &PullRequestSettings,
+    ) -> Result<PullRequestSettings, Error> {
+        self.set_project_merge_check(
+            project_key,
+            REQUIRED_APPROVERS_CHECK,
+            settings.required_approvers > 0,
+            Some(&RequiredApproversSettings {
+                enable: true,
+                required_count: HookCount(settings.required_approvers),
+            }),
+        )?;
+        self.set_project_merge_check::<()>(
+            project_key,
+            ALL_APPROVERS_CHECK,
+            settings.required_all_approvers,
+            None,
+        )?;
+        self.set_project_merge_check::<()>(
+            project_key,
+            INCOMPLETE_TASKS_CHECK,
+            settings.required_all_tasks_complete,
+            None,
+        )?;
+        self.set_project_merge_check(
+            project_key,
+            REQUIRED_BUILDS_CHECK,
+            settings.required_successful_builds > 0,
+            Some(&RequiredBuildsSettings {
+                required_count: HookCount(settings.required_successful_builds),
+            }),
+        )?;
+
+        self.project_pull_request_settings(project_key)
+    }
+
+    fn project_merge_check_enabled(&self, project_key: &str, check: &str) -> Result<bool, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}",
+            project_key, check
+        );
+
+        Ok(self.get::<MergeCheckState>(&url)?.enabled)
+    }
+
+    /// Returns `None` when the check is disabled.
+    fn project_merge_check<T>(&self, project_key: &str, check: &str) -> Result<Option<T>, Error>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        if !self.project_merge_check_enabled(project_key, check)? {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/settings",
+            project_key, check
+        );
+
+        self.send::<(), _>(Method::GET, &url, None)
+    }
+
+    fn set_project_merge_check<T: Serialize>(
+        &self,
+        project_key: &str,
+        check: &str,
+        enabled: bool,
+        settings: Option<&T>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/settings/hooks/{}/enabled",
+            project_key, check
+        );
+
+        if enabled {
+            self.put(&url, settings)
+        } else {
+            self.delete(&url)
+        }
+    }
+
+    pub fn repository_pull_request_settings(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<PullRequestSettings, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/pull-requests",
+            project_key, repository_slug
+        );
+
+        self.get(&url)
+    }
+
+    pub fn set_repository_pull_request_settings(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        settings: &PullRequestSettings,
+    ) -> Result<PullRequestSettings, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/settings/pull-requests",
+            project_key, repository_slug
+        );
+
+        self.post(&url, Some(settings))
+    }
+
+    pub fn apply_pull_request_settings(
+        &self,
+        project_filter: impl Fn(&Project) -> bool + Sync + Send,
+        repository_filter: impl Fn(&Repository) -> bool + Sync + Send,
+        settings: &PullRequestSettings,
+    ) -> Result<Vec<Repository>, Error> {
+        self.matching_repositories(project_filter, repository_filter)?
+            .map(|repository| {
+                let (_, repository) = repository?;
+                let current = self
+                    .repository_pull_request_settings(&repository.project.key, repository.slug())?;
+                if current != *settings {
+                    println!("Setting {} on {}", settings, repository);
+                    self.set_repository_pull_request_settings(
+                        &repository.project.key,
+                        repository.slug(),
+                        settings,
+                    )?;
+                }
+
+                Ok(repository)
+            })
+            .collect()
+    }
 }
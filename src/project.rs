@@ -32,7 +32,7 @@ impl Client {
     pub fn projects(&self) -> Result<Vec<Project>, Error> {
         let url = "rest/api/1.0/projects";
 
-        self.get_paged(&url)
+        self.get_paged(url)
     }
 
     pub fn project(&self, project_key: &str) -> Result<Project, Error> {
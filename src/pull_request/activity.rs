@@ -0,0 +1,263 @@
+use client::Client;
+use failure::Error;
+use pull_request::comment::Comment;
+use std::time::Duration;
+use user::User;
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "action", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PullRequestActivity {
+    #[serde(rename_all = "camelCase")]
+    Opened {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Updated {
+        id: u64,
+        created_date: u64,
+        user: User,
+        #[serde(default)]
+        added_reviewers: Vec<User>,
+        #[serde(default)]
+        removed_reviewers: Vec<User>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Rescoped {
+        id: u64,
+        created_date: u64,
+        user: User,
+        from_hash: String,
+        previous_from_hash: String,
+        to_hash: String,
+        previous_to_hash: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Commented {
+        id: u64,
+        created_date: u64,
+        user: User,
+        comment_action: String,
+        comment: Comment,
+    },
+    #[serde(rename_all = "camelCase")]
+    Approved {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Unapproved {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reviewed {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Merged {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Declined {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reopened {
+        id: u64,
+        created_date: u64,
+        user: User,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl PullRequestActivity {
+    pub fn id(&self) -> Option<u64> {
+        self.common().map(|(id, _, _)| id)
+    }
+
+    pub fn created_date(&self) -> Option<u64> {
+        self.common().map(|(_, created_date, _)| created_date)
+    }
+
+    pub fn user(&self) -> Option<&User> {
+        self.common().map(|(_, _, user)| user)
+    }
+
+    fn common(&self) -> Option<(u64, u64, &User)> {
+        match *self {
+            PullRequestActivity::Opened {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Updated {
+                id,
+                created_date,
+                ref user,
+                ..
+            }
+            | PullRequestActivity::Rescoped {
+                id,
+                created_date,
+                ref user,
+                ..
+            }
+            | PullRequestActivity::Commented {
+                id,
+                created_date,
+                ref user,
+                ..
+            }
+            | PullRequestActivity::Approved {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Unapproved {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Reviewed {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Merged {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Declined {
+                id,
+                created_date,
+                ref user,
+            }
+            | PullRequestActivity::Reopened {
+                id,
+                created_date,
+                ref user,
+            } => Some((id, created_date, user)),
+            PullRequestActivity::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PullRequestTimeline {
+    pub opened: Option<u64>,
+    pub first_review: Option<u64>,
+    pub first_approval: Option<u64>,
+    pub closed: Option<u64>,
+    pub rescopes: usize,
+}
+
+impl PullRequestTimeline {
+    /// Builds a timeline from activities in any order. Reviews are comments,
+    /// approvals and "needs work" reviews from anyone other than the author.
+    pub fn from_activities(activities: &[PullRequestActivity]) -> PullRequestTimeline {
+        let mut activities: Vec<&PullRequestActivity> = activities
+            .iter()
+            .filter(|a| a.created_date().is_some())
+            .collect();
+        activities.sort_by_key(|a| a.created_date());
+
+        let author = activities
+            .iter()
+            .filter_map(|a| match **a {
+                PullRequestActivity::Opened { ref user, .. } => Some(user),
+                _ => None,
+            })
+            .next();
+        let by_reviewer = |a: &PullRequestActivity| a.user() != author;
+
+        let mut timeline = PullRequestTimeline::default();
+        for activity in activities {
+            let date = activity.created_date();
+            match *activity {
+                PullRequestActivity::Opened { .. } => {
+                    timeline.opened = timeline.opened.or(date);
+                }
+                PullRequestActivity::Rescoped { .. } => {
+                    timeline.rescopes += 1;
+                }
+                PullRequestActivity::Commented { .. } | PullRequestActivity::Reviewed { .. }
+                    if by_reviewer(activity) =>
+                {
+                    timeline.first_review = timeline.first_review.or(date);
+                }
+                PullRequestActivity::Approved { .. } if by_reviewer(activity) => {
+                    timeline.first_review = timeline.first_review.or(date);
+                    timeline.first_approval = timeline.first_approval.or(date);
+                }
+                PullRequestActivity::Merged { .. } | PullRequestActivity::Declined { .. } => {
+                    timeline.closed = date;
+                }
+                PullRequestActivity::Reopened { .. } => {
+                    timeline.closed = None;
+                }
+                _ => {}
+            }
+        }
+
+        timeline
+    }
+
+    pub fn time_to_first_review(&self) -> Option<Duration> {
+        since(self.opened, self.first_review)
+    }
+
+    pub fn time_to_approval(&self) -> Option<Duration> {
+        since(self.opened, self.first_approval)
+    }
+
+    pub fn cycle_time(&self) -> Option<Duration> {
+        since(self.opened, self.closed)
+    }
+}
+
+fn since(start: Option<u64>, end: Option<u64>) -> Option<Duration> {
+    match (start, end) {
+        (Some(start), Some(end)) if end >= start => Some(Duration::from_millis(end - start)),
+        _ => None,
+    }
+}
+
+impl Client {
+    pub fn pull_request_activities(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<Vec<PullRequestActivity>, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/activities",
+            project_key, repository_slug, pull_request_id
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn pull_request_timeline(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<PullRequestTimeline, Error> {
+        let activities =
+            self.pull_request_activities(project_key, repository_slug, pull_request_id)?;
+
+        Ok(PullRequestTimeline::from_activities(&activities))
+    }
+}
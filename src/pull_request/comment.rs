@@ -0,0 +1,45 @@
+use std::fmt;
+use user::User;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    id: u64,
+    version: u64,
+    text: String,
+    author: User,
+    created_date: u64,
+    updated_date: u64,
+}
+
+impl fmt::Display for Comment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.author, self.text)
+    }
+}
+
+impl Comment {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn author(&self) -> &User {
+        &self.author
+    }
+
+    pub fn created_date(&self) -> u64 {
+        self.created_date
+    }
+
+    pub fn updated_date(&self) -> u64 {
+        self.updated_date
+    }
+}
@@ -0,0 +1,72 @@
+use client;
+use client::Client;
+use commit::Commit;
+use diff::{Change, Diff};
+use failure::Error;
+
+impl Client {
+    pub fn pull_request_diff(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+        since_id: Option<&str>,
+        until_id: Option<&str>,
+    ) -> Result<Diff, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/diff{}",
+            project_key,
+            repository_slug,
+            pull_request_id,
+            revisions(since_id, until_id)
+        );
+
+        self.get(&url)
+    }
+
+    pub fn pull_request_changes(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+        since_id: Option<&str>,
+        until_id: Option<&str>,
+    ) -> Result<Vec<Change>, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/changes{}",
+            project_key,
+            repository_slug,
+            pull_request_id,
+            revisions(since_id, until_id)
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn pull_request_commits(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<Vec<Commit>, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/commits",
+            project_key, repository_slug, pull_request_id
+        );
+
+        self.get_paged(&url)
+    }
+}
+
+fn revisions(since_id: Option<&str>, until_id: Option<&str>) -> String {
+    let params: Vec<String> = vec![("sinceId", since_id), ("untilId", until_id)]
+        .into_iter()
+        .filter_map(|(name, id)| id.map(|id| format!("{}={}", name, client::percent_encode(id))))
+        .collect();
+
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    }
+}
@@ -0,0 +1,78 @@
+pub mod activity;
+pub mod comment;
+pub mod diff;
+pub mod task;
+
+use repository::Repository;
+use std::fmt;
+use user::User;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub id: u64,
+    pub version: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: PullRequestState,
+    pub open: bool,
+    pub closed: bool,
+    pub created_date: u64,
+    pub updated_date: u64,
+    pub from_ref: PullRequestRef,
+    pub to_ref: PullRequestRef,
+    pub locked: bool,
+    pub author: Participant,
+    #[serde(default)]
+    pub reviewers: Vec<Participant>,
+    #[serde(default)]
+    pub participants: Vec<Participant>,
+}
+
+impl fmt::Display for PullRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} {}", self.id, self.title)
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PullRequestState {
+    Open,
+    Merged,
+    Declined,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestRef {
+    pub id: String,
+    pub display_id: String,
+    pub latest_commit: String,
+    pub repository: Repository,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    pub user: User,
+    pub role: ParticipantRole,
+    pub approved: bool,
+    pub status: ParticipantStatus,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParticipantRole {
+    Author,
+    Reviewer,
+    Participant,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParticipantStatus {
+    Approved,
+    Unapproved,
+    NeedsWork,
+}
@@ -0,0 +1,182 @@
+use client::Client;
+use failure::Error;
+use reqwest::Method;
+use std::fmt;
+use user::User;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    id: u64,
+    text: String,
+    state: TaskState,
+    author: User,
+    created_date: u64,
+    anchor: TaskAnchor,
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.state, self.text)
+    }
+}
+
+impl Task {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    pub fn author(&self) -> &User {
+        &self.author
+    }
+
+    pub fn created_date(&self) -> u64 {
+        self.created_date
+    }
+
+    pub fn comment_id(&self) -> u64 {
+        self.anchor.id
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == TaskState::Open
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TaskState {
+    Open,
+    Resolved,
+}
+
+impl fmt::Display for TaskState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TaskState::Open => write!(f, "OPEN"),
+            TaskState::Resolved => write!(f, "RESOLVED"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TaskAnchor {
+    id: u64,
+    #[serde(rename = "type", default = "comment_anchor")]
+    _type: String,
+}
+
+fn comment_anchor() -> String {
+    "COMMENT".to_owned()
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct NewTask<'a> {
+    anchor: TaskAnchor,
+    text: &'a str,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TaskTransition {
+    id: u64,
+    state: TaskState,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCount {
+    pub open: u64,
+    pub resolved: u64,
+}
+
+impl Client {
+    pub fn pull_request_tasks(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<Vec<Task>, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/tasks",
+            project_key, repository_slug, pull_request_id
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn pull_request_task_count(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<TaskCount, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/pull-requests/{}/tasks/count",
+            project_key, repository_slug, pull_request_id
+        );
+
+        self.get(&url)
+    }
+
+    pub fn pull_request_open_task_count(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        pull_request_id: u64,
+    ) -> Result<u64, Error> {
+        Ok(self
+            .pull_request_task_count(project_key, repository_slug, pull_request_id)?
+            .open)
+    }
+
+    pub fn task(&self, task_id: u64) -> Result<Task, Error> {
+        let url = format!("rest/api/1.0/tasks/{}", task_id);
+
+        self.get(&url)
+    }
+
+    pub fn create_task(&self, comment_id: u64, text: &str) -> Result<Task, Error> {
+        let url = "rest/api/1.0/tasks";
+        let task = NewTask {
+            anchor: TaskAnchor {
+                id: comment_id,
+                _type: comment_anchor(),
+            },
+            text,
+        };
+
+        self.post(url, Some(&task))
+    }
+
+    pub fn resolve_task(&self, task_id: u64) -> Result<Task, Error> {
+        self.set_task_state(task_id, TaskState::Resolved)
+    }
+
+    pub fn reopen_task(&self, task_id: u64) -> Result<Task, Error> {
+        self.set_task_state(task_id, TaskState::Open)
+    }
+
+    pub fn set_task_state(&self, task_id: u64, state: TaskState) -> Result<Task, Error> {
+        let url = format!("rest/api/1.0/tasks/{}", task_id);
+        let transition = TaskTransition { id: task_id, state };
+
+        self.send(Method::PUT, &url, Some(&transition))
+    }
+
+    pub fn delete_task(&self, task_id: u64) -> Result<(), Error> {
+        let url = format!("rest/api/1.0/tasks/{}", task_id);
+
+        self.delete(&url)
+    }
+}
@@ -0,0 +1,46 @@
+/// The changes that bring a list of live items in line with a desired list.
+pub(crate) struct Reconciliation<'a, C, D: 'a> {
+    /// Live items to change into the paired desired item.
+    pub updates: Vec<(C, &'a D)>,
+    /// Desired items with no live item to change.
+    pub additions: Vec<&'a D>,
+    /// Live items no desired item was paired with, including duplicates.
+    pub deletions: Vec<C>,
+}
+
+/// Pairs each desired item with at most one live item, first taking a live
+/// item that already `satisfies` it and then one it merely `applies_to`.
+/// Each live item is used at most once, so duplicates for the same desired
+/// item are left over to be deleted.
+pub(crate) fn reconcile<'a, C, D>(
+    current: Vec<C>,
+    desired: &'a [D],
+    applies_to: impl Fn(&C, &D) -> bool,
+    satisfies: impl Fn(&C, &D) -> bool,
+) -> Reconciliation<'a, C, D> {
+    let mut pool = current;
+    let mut unmatched = Vec::new();
+    for item in desired {
+        match pool.iter().position(|c| satisfies(c, item)) {
+            Some(i) => {
+                pool.remove(i);
+            }
+            None => unmatched.push(item),
+        }
+    }
+
+    let mut updates = Vec::new();
+    let mut additions = Vec::new();
+    for item in unmatched {
+        match pool.iter().position(|c| applies_to(c, item)) {
+            Some(i) => updates.push((pool.remove(i), item)),
+            None => additions.push(item),
+        }
+    }
+
+    Reconciliation {
+        updates,
+        additions,
+        deletions: pool,
+    }
+}
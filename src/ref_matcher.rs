@@ -0,0 +1,246 @@
+use branch_model::BranchModel;
+use failure::Error;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefMatcher {
+    active: bool,
+    id: String,
+    display_id: String,
+    #[serde(rename = "type")]
+    kind: RefMatcherKind,
+}
+
+impl fmt::Display for RefMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.kind.name(), self.display_id)
+    }
+}
+
+impl RefMatcher {
+    pub fn new(kind: RefMatcherKind, id: &str, display_id: &str) -> RefMatcher {
+        RefMatcher {
+            active: true,
+            id: id.to_owned(),
+            display_id: display_id.to_owned(),
+            kind,
+        }
+    }
+
+    pub fn any_ref() -> RefMatcher {
+        RefMatcher::new(
+            RefMatcherKind::AnyRef,
+            "ANY_REF_MATCHER_ID",
+            "ANY_REF_MATCHER_ID",
+        )
+    }
+
+    pub fn branch(name: &str) -> RefMatcher {
+        let name = unqualify(name);
+
+        RefMatcher::new(RefMatcherKind::Branch, &qualify(name), name)
+    }
+
+    pub fn pattern(pattern: &str) -> RefMatcher {
+        RefMatcher::new(RefMatcherKind::Pattern, pattern, pattern)
+    }
+
+    pub fn model_category(category: &str) -> RefMatcher {
+        RefMatcher::new(RefMatcherKind::ModelCategory, category, category)
+    }
+
+    pub fn model_branch(branch: &str) -> RefMatcher {
+        RefMatcher::new(RefMatcherKind::ModelBranch, branch, branch)
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn display_id(&self) -> &str {
+        &self.display_id
+    }
+
+    pub fn kind(&self) -> RefMatcherKind {
+        self.kind
+    }
+
+    pub fn same_ref(&self, other: &RefMatcher) -> bool {
+        self.kind == other.kind && self.id == other.id
+    }
+
+    /// Whether [`matches`](#method.matches) needs a branching model.
+    pub fn uses_branch_model(&self) -> bool {
+        match self.kind {
+            RefMatcherKind::ModelCategory | RefMatcherKind::ModelBranch => true,
+            _ => false,
+        }
+    }
+
+    /// Evaluates the matcher locally against a branch, given either as a
+    /// display name (`master`) or a fully qualified ref (`refs/heads/master`).
+    /// Branching model matchers never match without a `branch_model`.
+    pub fn matches(&self, branch_name: &str, branch_model: Option<&BranchModel>) -> bool {
+        let ref_id = qualify(branch_name);
+
+        match self.kind {
+            RefMatcherKind::AnyRef => true,
+            RefMatcherKind::Branch => qualify(&self.id) == ref_id,
+            RefMatcherKind::Pattern => pattern_matches(&self.id, &ref_id),
+            RefMatcherKind::ModelCategory => branch_model
+                .and_then(|model| model.classify(&ref_id))
+                .map_or(false, |category| category.to_string() == self.id),
+            RefMatcherKind::ModelBranch => branch_model.map_or(false, |model| {
+                let branch = match self.id.as_str() {
+                    "development" => model.development.as_ref(),
+                    "production" => model.production.as_ref(),
+                    _ => None,
+                };
+                branch.map_or(false, |b| qualify(&b.id) == ref_id)
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RefMatcherKind {
+    AnyRef,
+    Branch,
+    Pattern,
+    ModelCategory,
+    ModelBranch,
+}
+
+impl RefMatcherKind {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            RefMatcherKind::AnyRef => "Any branch",
+            RefMatcherKind::Branch => "Branch",
+            RefMatcherKind::Pattern => "Pattern",
+            RefMatcherKind::ModelCategory => "Branching model category",
+            RefMatcherKind::ModelBranch => "Branching model branch",
+        }
+    }
+}
+
+impl FromStr for RefMatcherKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<RefMatcherKind, Error> {
+        match s {
+            "ANY_REF" => Ok(RefMatcherKind::AnyRef),
+            "BRANCH" => Ok(RefMatcherKind::Branch),
+            "PATTERN" => Ok(RefMatcherKind::Pattern),
+            "MODEL_CATEGORY" => Ok(RefMatcherKind::ModelCategory),
+            "MODEL_BRANCH" => Ok(RefMatcherKind::ModelBranch),
+            _ => bail!("unexpected ref matcher type {}", s),
+        }
+    }
+}
+
+impl fmt::Display for RefMatcherKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RefMatcherKind::AnyRef => write!(f, "ANY_REF"),
+            RefMatcherKind::Branch => write!(f, "BRANCH"),
+            RefMatcherKind::Pattern => write!(f, "PATTERN"),
+            RefMatcherKind::ModelCategory => write!(f, "MODEL_CATEGORY"),
+            RefMatcherKind::ModelBranch => write!(f, "MODEL_BRANCH"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MatcherType {
+    id: String,
+    name: String,
+}
+
+impl<'de> Deserialize<'de> for RefMatcherKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let matcher_type = MatcherType::deserialize(deserializer)?;
+        FromStr::from_str(&matcher_type.id).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for RefMatcherKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        MatcherType {
+            id: self.to_string(),
+            name: self.name().to_owned(),
+        }
+        .serialize(serializer)
+    }
+}
+
+pub(crate) fn unqualify(ref_id: &str) -> &str {
+    if ref_id.starts_with("refs/heads/") {
+        &ref_id["refs/heads/".len()..]
+    } else {
+        ref_id
+    }
+}
+
+pub(crate) fn qualify(branch_name: &str) -> String {
+    if branch_name.starts_with("refs/") {
+        branch_name.to_owned()
+    } else {
+        format!("refs/heads/{}", branch_name)
+    }
+}
+
+// Bitbucket patterns are ant-style: `?` and `*` match within a path segment,
+// `**` matches any number of segments, a trailing `/` matches everything
+// below it, and patterns not starting with `refs/` may match at any depth.
+fn pattern_matches(pattern: &str, ref_id: &str) -> bool {
+    let mut pattern = pattern.to_owned();
+    if pattern.ends_with('/') {
+        pattern.push_str("**");
+    }
+    if !pattern.starts_with("refs/") && !pattern.starts_with("**") {
+        pattern = format!("**/{}", pattern);
+    }
+
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = ref_id.split('/').collect();
+
+    segments_match(&pattern, &path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| segments_match(rest, &path[i..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                let segment: Vec<char> = segment.chars().collect();
+                let name: Vec<char> = name.chars().collect();
+                wildcard_match(&segment, &name) && segments_match(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| wildcard_match(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
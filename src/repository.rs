@@ -1,6 +1,8 @@
 use client::Client;
 use failure::Error;
-use project::ProjectRef;
+use project::{Project, ProjectRef};
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
 use std::fmt;
 use std::path::PathBuf;
 
@@ -31,6 +33,32 @@ impl Client {
         self.get_paged(&url)
     }
 
+    /// Walks, in parallel, every repository accepted by `repository_filter`
+    /// in the projects accepted by `project_filter`. A project whose
+    /// repositories cannot be listed yields a single error.
+    pub fn matching_repositories<'a, P, R>(
+        &'a self,
+        project_filter: P,
+        repository_filter: R,
+    ) -> Result<impl ParallelIterator<Item = Result<(Project, Repository), Error>> + 'a, Error>
+    where
+        P: Fn(&Project) -> bool + Sync + Send + 'a,
+        R: Fn(&Repository) -> bool + Sync + Send + 'a,
+    {
+        let projects = self.projects()?.into_par_iter().filter(project_filter);
+
+        Ok(
+            projects.flat_map(move |project| match self.repositories(project.key()) {
+                Ok(repositories) => repositories
+                    .into_par_iter()
+                    .filter(|r| repository_filter(r))
+                    .map(|r| Ok((project.clone(), r)))
+                    .collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            }),
+        )
+    }
+
     pub fn repository(
         &self,
         project_key: &str,
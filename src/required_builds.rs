@@ -0,0 +1,185 @@
+use client::Client;
+use failure::Error;
+use itertools::Itertools;
+use reconcile::reconcile;
+use ref_matcher::RefMatcher;
+use reqwest::Method;
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredBuild {
+    id: u64,
+    build_parent_keys: Vec<String>,
+    ref_matcher: RefMatcher,
+    exempt_ref_matcher: Option<RefMatcher>,
+}
+
+impl fmt::Display for RequiredBuild {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] on {}",
+            self.build_parent_keys.iter().join(", "),
+            self.ref_matcher
+        )
+    }
+}
+
+impl RequiredBuild {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn build_parent_keys(&self) -> &[String] {
+        &self.build_parent_keys
+    }
+
+    pub fn ref_matcher(&self) -> &RefMatcher {
+        &self.ref_matcher
+    }
+
+    pub fn exempt_ref_matcher(&self) -> Option<&RefMatcher> {
+        self.exempt_ref_matcher.as_ref()
+    }
+
+    fn applies_to(&self, required_build: &NewRequiredBuild) -> bool {
+        self.ref_matcher.same_ref(&required_build.ref_matcher)
+    }
+
+    fn satisfies(&self, required_build: &NewRequiredBuild) -> bool {
+        let keys: HashSet<&String> = self.build_parent_keys.iter().collect();
+        let exempt_matches = match (&self.exempt_ref_matcher, &required_build.exempt_ref_matcher) {
+            (Some(current), Some(desired)) => current.same_ref(desired),
+            (None, None) => true,
+            _ => false,
+        };
+
+        self.applies_to(required_build)
+            && keys == required_build.build_parent_keys.iter().collect()
+            && exempt_matches
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRequiredBuild {
+    pub build_parent_keys: Vec<String>,
+    pub ref_matcher: RefMatcher,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exempt_ref_matcher: Option<RefMatcher>,
+}
+
+impl fmt::Display for NewRequiredBuild {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] on {}",
+            self.build_parent_keys.iter().join(", "),
+            self.ref_matcher
+        )
+    }
+}
+
+impl Client {
+    pub fn required_builds(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<Vec<RequiredBuild>, Error> {
+        let url = format!(
+            "rest/required-builds/latest/projects/{}/repos/{}/conditions",
+            project_key, repository_slug
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn create_required_build(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        required_build: &NewRequiredBuild,
+    ) -> Result<RequiredBuild, Error> {
+        let url = format!(
+            "rest/required-builds/latest/projects/{}/repos/{}/condition",
+            project_key, repository_slug
+        );
+
+        self.post(&url, Some(required_build))
+    }
+
+    pub fn update_required_build(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        required_build_id: u64,
+        required_build: &NewRequiredBuild,
+    ) -> Result<RequiredBuild, Error> {
+        let url = format!(
+            "rest/required-builds/latest/projects/{}/repos/{}/condition/{}",
+            project_key, repository_slug, required_build_id
+        );
+
+        self.send(Method::PUT, &url, Some(required_build))
+    }
+
+    pub fn delete_required_build(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        required_build_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/required-builds/latest/projects/{}/repos/{}/condition/{}",
+            project_key, repository_slug, required_build_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn set_required_builds(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        required_builds: Vec<NewRequiredBuild>,
+    ) -> Result<(), Error> {
+        let current = self.required_builds(project_key, repository_slug)?;
+        let reconciliation = reconcile(
+            current,
+            &required_builds,
+            RequiredBuild::applies_to,
+            RequiredBuild::satisfies,
+        );
+
+        for build in &reconciliation.deletions {
+            println!("Deleting {}", build);
+            self.delete_required_build(project_key, repository_slug, build.id)?;
+        }
+
+        for (build, required_build) in reconciliation.updates {
+            println!("Updating {}", required_build);
+            self.update_required_build(project_key, repository_slug, build.id, required_build)?;
+        }
+
+        for required_build in reconciliation.additions {
+            println!("Adding {}", required_build);
+            self.create_required_build(project_key, repository_slug, required_build)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_project_required_builds(
+        &self,
+        project_key: &str,
+        required_builds: Vec<NewRequiredBuild>,
+    ) -> Result<(), Error> {
+        for repository in self.repositories(project_key)? {
+            self.set_required_builds(project_key, repository.slug(), required_builds.clone())?;
+        }
+
+        Ok(())
+    }
+}
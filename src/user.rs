@@ -1,9 +1,29 @@
-use client::Client;
+use auth::permission::{GlobalPermission, ProjectPermission, RepositoryPermission};
+use client::{Client, Page};
 
 use failure::Error;
+use reqwest::Method;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use url::form_urlencoded;
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UserType {
+    Normal,
+    Service,
+}
+
+impl fmt::Display for UserType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserType::Normal => write!(f, "NORMAL"),
+            UserType::Service => write!(f, "SERVICE"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
     name: String,
@@ -13,7 +33,38 @@ pub struct User {
     active: bool,
     slug: String,
     #[serde(rename = "type")]
-    _type: String,
+    _type: UserType,
+    /// Only reported by the admin endpoints.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_authentication_timestamp: Option<u64>,
+}
+
+// The authentication timestamp depends on which endpoint the user came from,
+// so it is left out of equality to keep access reconciliation stable.
+impl PartialEq for User {
+    fn eq(&self, other: &User) -> bool {
+        self.name == other.name
+            && self.email_address == other.email_address
+            && self.id == other.id
+            && self.display_name == other.display_name
+            && self.active == other.active
+            && self.slug == other.slug
+            && self._type == other._type
+    }
+}
+
+impl Eq for User {}
+
+impl Hash for User {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.email_address.hash(state);
+        self.id.hash(state);
+        self.display_name.hash(state);
+        self.active.hash(state);
+        self.slug.hash(state);
+        self._type.hash(state);
+    }
 }
 
 impl fmt::Display for User {
@@ -22,14 +73,180 @@ impl fmt::Display for User {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct NewUser {
+    pub name: String,
+    pub display_name: String,
+    pub email_address: String,
+    /// May be omitted when `notify` is set; the user then chooses their own.
+    pub password: Option<String>,
+    pub add_to_default_group: bool,
+    pub notify: bool,
+}
+
+impl NewUser {
+    pub fn new(name: &str, display_name: &str, email_address: &str) -> NewUser {
+        NewUser {
+            name: name.to_owned(),
+            display_name: display_name.to_owned(),
+            email_address: email_address.to_owned(),
+            password: None,
+            add_to_default_group: true,
+            notify: true,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct UserUpdate<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<&'a str>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct UserRename<'a> {
+    name: &'a str,
+    new_name: &'a str,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PasswordUpdate<'a> {
+    name: &'a str,
+    password: &'a str,
+    password_confirm: &'a str,
+}
+
+/// A permission qualifier for [`UserSearch`]; users must hold at least the
+/// given permission.
+#[derive(Debug)]
+pub enum PermissionFilter {
+    Global(GlobalPermission),
+    Project {
+        project_key: String,
+        permission: ProjectPermission,
+    },
+    Repository {
+        project_key: String,
+        repository_slug: String,
+        permission: RepositoryPermission,
+    },
+}
+
+impl PermissionFilter {
+    fn append_to(&self, prefix: &str, query: &mut form_urlencoded::Serializer<String>) {
+        match self {
+            PermissionFilter::Global(permission) => {
+                query.append_pair(prefix, &permission.to_string());
+            }
+            PermissionFilter::Project {
+                project_key,
+                permission,
+            } => {
+                query
+                    .append_pair(prefix, &permission.to_string())
+                    .append_pair(&format!("{}.projectKey", prefix), project_key);
+            }
+            PermissionFilter::Repository {
+                project_key,
+                repository_slug,
+                permission,
+            } => {
+                query
+                    .append_pair(prefix, &permission.to_string())
+                    .append_pair(&format!("{}.projectKey", prefix), project_key)
+                    .append_pair(&format!("{}.repositorySlug", prefix), repository_slug);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UserSearch {
+    /// Matched against user names, display names and email addresses.
+    pub filter: Option<String>,
+    pub group: Option<String>,
+    pub permissions: Vec<PermissionFilter>,
+}
+
+impl UserSearch {
+    fn query(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        if let Some(ref filter) = self.filter {
+            query.append_pair("filter", filter);
+        }
+        if let Some(ref group) = self.group {
+            query.append_pair("group", group);
+        }
+        for (i, permission) in self.permissions.iter().enumerate() {
+            let prefix = match i {
+                0 => "permission".to_owned(),
+                i => format!("permission.{}", i),
+            };
+            permission.append_to(&prefix, &mut query);
+        }
+
+        query.finish()
+    }
+}
+
+fn name_query(user_name: &str) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .append_pair("name", user_name)
+        .finish()
+}
+
 impl Client {
     pub fn users_admin(&self) -> Result<Vec<User>, Error> {
         let url = "rest/api/1.0/admin/users";
-        self.get_paged(&url)
+        self.get_paged(url)
     }
 
     pub fn users(&self) -> Result<Vec<User>, Error> {
         let url = "rest/api/1.0/users";
+        self.get_paged(url)
+    }
+
+    pub fn search_users(&self, search: &UserSearch) -> Result<Vec<User>, Error> {
+        let query = search.query();
+        if query.is_empty() {
+            return self.users();
+        }
+
+        let url = format!("rest/api/1.0/users?{}", query);
+        self.get_paged(&url)
+    }
+
+    /// Fetches one page of a user search, for servers with too many users
+    /// to list at once.
+    pub fn search_users_page(
+        &self,
+        search: &UserSearch,
+        start: u64,
+        limit: u64,
+    ) -> Result<Page<User>, Error> {
+        let query = search.query();
+        let url = if query.is_empty() {
+            "rest/api/1.0/users".to_owned()
+        } else {
+            format!("rest/api/1.0/users?{}", query)
+        };
+
+        self.get_page(&url, start, limit)
+    }
+
+    pub fn search_users_admin(&self, filter: &str) -> Result<Vec<User>, Error> {
+        let url = format!(
+            "rest/api/1.0/admin/users?{}",
+            form_urlencoded::Serializer::new(String::new())
+                .append_pair("filter", filter)
+                .finish()
+        );
         self.get_paged(&url)
     }
 
@@ -37,10 +254,108 @@ impl Client {
         let url = format!("rest/api/1.0/users/{}", user_slug);
         self.get(&url)
     }
+
+    pub fn create_user(&self, user: &NewUser) -> Result<(), Error> {
+        if user.password.is_none() && !user.notify {
+            bail!(
+                "A password is required to create {} without notifying them",
+                user.name
+            );
+        }
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("name", &user.name)
+            .append_pair("displayName", &user.display_name)
+            .append_pair("emailAddress", &user.email_address)
+            .append_pair("addToDefaultGroup", &user.add_to_default_group.to_string())
+            .append_pair("notify", &user.notify.to_string());
+        if let Some(ref password) = user.password {
+            query.append_pair("password", password);
+        }
+
+        let url = format!("rest/api/1.0/admin/users?{}", query.finish());
+        self.post::<(), ()>(&url, None)
+    }
+
+    pub fn update_user(
+        &self,
+        user_name: &str,
+        display_name: Option<&str>,
+        email_address: Option<&str>,
+    ) -> Result<User, Error> {
+        let url = "rest/api/1.0/admin/users";
+        let update = UserUpdate {
+            name: user_name,
+            display_name,
+            email: email_address,
+        };
+
+        self.send(Method::PUT, url, Some(&update))
+    }
+
+    pub fn rename_user(&self, user_name: &str, new_name: &str) -> Result<User, Error> {
+        let url = "rest/api/1.0/admin/users/rename";
+        let rename = UserRename {
+            name: user_name,
+            new_name,
+        };
+
+        self.post(url, Some(&rename))
+    }
+
+    pub fn delete_user(&self, user_name: &str) -> Result<User, Error> {
+        let url = format!("rest/api/1.0/admin/users?{}", name_query(user_name));
+        self.send::<(), _>(Method::DELETE, &url, None)
+    }
+
+    pub fn set_user_password(&self, user_name: &str, password: &str) -> Result<(), Error> {
+        let url = "rest/api/1.0/admin/users/credentials";
+        let update = PasswordUpdate {
+            name: user_name,
+            password,
+            password_confirm: password,
+        };
+
+        self.put(url, Some(&update))
+    }
+
+    pub fn clear_user_captcha(&self, user_name: &str) -> Result<(), Error> {
+        let url = format!("rest/api/1.0/admin/users/captcha?{}", name_query(user_name));
+        self.delete(&url)
+    }
 }
 
 impl User {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn email_address(&self) -> Option<&str> {
+        self.email_address.as_ref().map(String::as_str)
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn user_type(&self) -> UserType {
+        self._type
+    }
+
+    pub fn last_authentication_timestamp(&self) -> Option<u64> {
+        self.last_authentication_timestamp
+    }
 }
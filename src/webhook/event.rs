@@ -0,0 +1,145 @@
+use failure::Error;
+use pull_request::comment::Comment;
+use pull_request::{Participant, ParticipantStatus, PullRequest};
+use repository::Repository;
+use serde_json;
+use user::User;
+use webhook::WebhookEventKey;
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "eventKey")]
+pub enum WebhookEvent {
+    #[serde(rename = "repo:refs_changed")]
+    RefsChanged(RefsChangedEvent),
+    #[serde(rename = "pr:opened")]
+    PullRequestOpened(PullRequestEvent),
+    #[serde(rename = "pr:modified")]
+    PullRequestModified(PullRequestEvent),
+    #[serde(rename = "pr:merged")]
+    PullRequestMerged(PullRequestEvent),
+    #[serde(rename = "pr:declined")]
+    PullRequestDeclined(PullRequestEvent),
+    #[serde(rename = "pr:deleted")]
+    PullRequestDeleted(PullRequestEvent),
+    #[serde(rename = "pr:reviewer:approved")]
+    ReviewerApproved(ReviewerEvent),
+    #[serde(rename = "pr:reviewer:unapproved")]
+    ReviewerUnapproved(ReviewerEvent),
+    #[serde(rename = "pr:reviewer:needs_work")]
+    ReviewerNeedsWork(ReviewerEvent),
+    #[serde(rename = "pr:comment:added")]
+    CommentAdded(CommentEvent),
+    #[serde(rename = "pr:comment:edited")]
+    CommentEdited(CommentEvent),
+    #[serde(rename = "pr:comment:deleted")]
+    CommentDeleted(CommentEvent),
+    #[serde(rename = "diagnostics:ping")]
+    Ping,
+    #[serde(other)]
+    Other,
+}
+
+impl WebhookEvent {
+    /// Parses a delivery from its `X-Event-Key` header and body. Pings carry
+    /// no `eventKey` in their body, so they are recognised from the header.
+    pub fn parse(event_key: &str, body: &[u8]) -> Result<WebhookEvent, Error> {
+        if event_key.parse::<WebhookEventKey>()? == WebhookEventKey::Ping {
+            return Ok(WebhookEvent::Ping);
+        }
+
+        Ok(serde_json::from_slice(body)?)
+    }
+
+    /// The subscription key for this event, or `None` for events this crate
+    /// does not model.
+    pub fn key(&self) -> Option<WebhookEventKey> {
+        match *self {
+            WebhookEvent::RefsChanged(_) => Some(WebhookEventKey::RefsChanged),
+            WebhookEvent::PullRequestOpened(_) => Some(WebhookEventKey::PullRequestOpened),
+            WebhookEvent::PullRequestModified(_) => Some(WebhookEventKey::PullRequestModified),
+            WebhookEvent::PullRequestMerged(_) => Some(WebhookEventKey::PullRequestMerged),
+            WebhookEvent::PullRequestDeclined(_) => Some(WebhookEventKey::PullRequestDeclined),
+            WebhookEvent::PullRequestDeleted(_) => Some(WebhookEventKey::PullRequestDeleted),
+            WebhookEvent::ReviewerApproved(_) => Some(WebhookEventKey::ReviewerApproved),
+            WebhookEvent::ReviewerUnapproved(_) => Some(WebhookEventKey::ReviewerUnapproved),
+            WebhookEvent::ReviewerNeedsWork(_) => Some(WebhookEventKey::ReviewerNeedsWork),
+            WebhookEvent::CommentAdded(_) => Some(WebhookEventKey::CommentAdded),
+            WebhookEvent::CommentEdited(_) => Some(WebhookEventKey::CommentEdited),
+            WebhookEvent::CommentDeleted(_) => Some(WebhookEventKey::CommentDeleted),
+            WebhookEvent::Ping => Some(WebhookEventKey::Ping),
+            WebhookEvent::Other => None,
+        }
+    }
+
+    pub fn pull_request(&self) -> Option<&PullRequest> {
+        match *self {
+            WebhookEvent::PullRequestOpened(ref e)
+            | WebhookEvent::PullRequestModified(ref e)
+            | WebhookEvent::PullRequestMerged(ref e)
+            | WebhookEvent::PullRequestDeclined(ref e)
+            | WebhookEvent::PullRequestDeleted(ref e) => Some(&e.pull_request),
+            WebhookEvent::ReviewerApproved(ref e)
+            | WebhookEvent::ReviewerUnapproved(ref e)
+            | WebhookEvent::ReviewerNeedsWork(ref e) => Some(&e.pull_request),
+            WebhookEvent::CommentAdded(ref e)
+            | WebhookEvent::CommentEdited(ref e)
+            | WebhookEvent::CommentDeleted(ref e) => Some(&e.pull_request),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefsChangedEvent {
+    pub date: String,
+    pub actor: User,
+    pub repository: Repository,
+    pub changes: Vec<RefChange>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RefChange {
+    pub ref_id: String,
+    pub from_hash: String,
+    pub to_hash: String,
+    #[serde(rename = "type")]
+    pub change_type: RefChangeType,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RefChangeType {
+    Add,
+    Update,
+    Delete,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestEvent {
+    pub date: String,
+    pub actor: User,
+    pub pull_request: PullRequest,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewerEvent {
+    pub date: String,
+    pub actor: User,
+    pub pull_request: PullRequest,
+    pub participant: Participant,
+    pub previous_status: Option<ParticipantStatus>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentEvent {
+    pub date: String,
+    pub actor: User,
+    pub pull_request: PullRequest,
+    pub comment: Comment,
+    pub comment_parent_id: Option<u64>,
+}
@@ -0,0 +1,396 @@
+pub mod event;
+pub mod receiver;
+
+use client::Client;
+use failure::Error;
+use itertools::Itertools;
+use reqwest::Method;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use url::form_urlencoded;
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    #[serde(skip_serializing)]
+    pub id: Option<u64>,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<WebhookEventKey>,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub configuration: WebhookConfiguration,
+    #[serde(default = "ssl_verification_required")]
+    pub ssl_verification_required: bool,
+}
+
+fn ssl_verification_required() -> bool {
+    true
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// The key identifying a kind of event, as used in webhook subscriptions
+/// and the `X-Event-Key` header. Keys this crate does not model are kept
+/// verbatim in `Other`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WebhookEventKey {
+    RefsChanged,
+    PullRequestOpened,
+    PullRequestModified,
+    PullRequestMerged,
+    PullRequestDeclined,
+    PullRequestDeleted,
+    ReviewerApproved,
+    ReviewerUnapproved,
+    ReviewerNeedsWork,
+    CommentAdded,
+    CommentEdited,
+    CommentDeleted,
+    Ping,
+    Other(String),
+}
+
+impl FromStr for WebhookEventKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<WebhookEventKey, Error> {
+        Ok(match s {
+            "repo:refs_changed" => WebhookEventKey::RefsChanged,
+            "pr:opened" => WebhookEventKey::PullRequestOpened,
+            "pr:modified" => WebhookEventKey::PullRequestModified,
+            "pr:merged" => WebhookEventKey::PullRequestMerged,
+            "pr:declined" => WebhookEventKey::PullRequestDeclined,
+            "pr:deleted" => WebhookEventKey::PullRequestDeleted,
+            "pr:reviewer:approved" => WebhookEventKey::ReviewerApproved,
+            "pr:reviewer:unapproved" => WebhookEventKey::ReviewerUnapproved,
+            "pr:reviewer:needs_work" => WebhookEventKey::ReviewerNeedsWork,
+            "pr:comment:added" => WebhookEventKey::CommentAdded,
+            "pr:comment:edited" => WebhookEventKey::CommentEdited,
+            "pr:comment:deleted" => WebhookEventKey::CommentDeleted,
+            "diagnostics:ping" => WebhookEventKey::Ping,
+            _ => WebhookEventKey::Other(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for WebhookEventKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WebhookEventKey::RefsChanged => write!(f, "repo:refs_changed"),
+            WebhookEventKey::PullRequestOpened => write!(f, "pr:opened"),
+            WebhookEventKey::PullRequestModified => write!(f, "pr:modified"),
+            WebhookEventKey::PullRequestMerged => write!(f, "pr:merged"),
+            WebhookEventKey::PullRequestDeclined => write!(f, "pr:declined"),
+            WebhookEventKey::PullRequestDeleted => write!(f, "pr:deleted"),
+            WebhookEventKey::ReviewerApproved => write!(f, "pr:reviewer:approved"),
+            WebhookEventKey::ReviewerUnapproved => write!(f, "pr:reviewer:unapproved"),
+            WebhookEventKey::ReviewerNeedsWork => write!(f, "pr:reviewer:needs_work"),
+            WebhookEventKey::CommentAdded => write!(f, "pr:comment:added"),
+            WebhookEventKey::CommentEdited => write!(f, "pr:comment:edited"),
+            WebhookEventKey::CommentDeleted => write!(f, "pr:comment:deleted"),
+            WebhookEventKey::Ping => write!(f, "diagnostics:ping"),
+            WebhookEventKey::Other(ref key) => write!(f, "{}", key),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WebhookEventKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let key = String::deserialize(deserializer)?;
+        FromStr::from_str(&key).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for WebhookEventKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} [{}]",
+            self.name,
+            self.url,
+            self.events.iter().join(", ")
+        )
+    }
+}
+
+impl Webhook {
+    pub fn new(name: &str, url: &str, events: &[WebhookEventKey]) -> Webhook {
+        Webhook {
+            id: None,
+            name: name.to_owned(),
+            url: url.to_owned(),
+            events: events.to_vec(),
+            active: true,
+            configuration: WebhookConfiguration::default(),
+            ssl_verification_required: true,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTestResult {
+    pub request: Option<Value>,
+    pub response: Option<WebhookTestResponse>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTestResponse {
+    pub status_code: u16,
+    pub body: Option<String>,
+}
+
+impl WebhookTestResult {
+    pub fn is_success(&self) -> bool {
+        self.response
+            .as_ref()
+            .map_or(false, |r| r.status_code >= 200 && r.status_code < 300)
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookStatistics {
+    pub last_success: Option<WebhookInvocation>,
+    pub last_failure: Option<WebhookInvocation>,
+    pub last_error: Option<WebhookInvocation>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInvocation {
+    pub id: u64,
+    pub event: String,
+    pub start: u64,
+    pub finish: u64,
+    pub result: WebhookInvocationResult,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInvocationResult {
+    pub outcome: String,
+    pub description: Option<String>,
+}
+
+impl WebhookStatistics {
+    pub fn latest(&self) -> Option<&WebhookInvocation> {
+        vec![&self.last_success, &self.last_failure, &self.last_error]
+            .into_iter()
+            .filter_map(Option::as_ref)
+            .max_by_key(|i| i.finish)
+    }
+}
+
+fn test_query(webhook_url: &str) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .append_pair("url", webhook_url)
+        .finish()
+}
+
+impl Client {
+    pub fn project_webhooks(&self, project_key: &str) -> Result<Vec<Webhook>, Error> {
+        let url = format!("rest/api/1.0/projects/{}/webhooks", project_key);
+
+        self.get_paged(&url)
+    }
+
+    pub fn create_project_webhook(
+        &self,
+        project_key: &str,
+        webhook: &Webhook,
+    ) -> Result<Webhook, Error> {
+        let url = format!("rest/api/1.0/projects/{}/webhooks", project_key);
+
+        self.post(&url, Some(webhook))
+    }
+
+    pub fn update_project_webhook(
+        &self,
+        project_key: &str,
+        webhook_id: u64,
+        webhook: &Webhook,
+    ) -> Result<Webhook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/webhooks/{}",
+            project_key, webhook_id
+        );
+
+        self.send(Method::PUT, &url, Some(webhook))
+    }
+
+    pub fn delete_project_webhook(&self, project_key: &str, webhook_id: u64) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/webhooks/{}",
+            project_key, webhook_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn project_webhook_statistics(
+        &self,
+        project_key: &str,
+        webhook_id: u64,
+    ) -> Result<WebhookStatistics, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/webhooks/{}/statistics",
+            project_key, webhook_id
+        );
+
+        self.get(&url)
+    }
+
+    pub fn test_project_webhook(
+        &self,
+        project_key: &str,
+        webhook_url: &str,
+    ) -> Result<WebhookTestResult, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/webhooks/test?{}",
+            project_key,
+            test_query(webhook_url)
+        );
+
+        self.post::<(), _>(&url, None)
+    }
+
+    pub fn repository_webhooks(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+    ) -> Result<Vec<Webhook>, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks",
+            project_key, repository_slug
+        );
+
+        self.get_paged(&url)
+    }
+
+    pub fn create_repository_webhook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        webhook: &Webhook,
+    ) -> Result<Webhook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks",
+            project_key, repository_slug
+        );
+
+        self.post(&url, Some(webhook))
+    }
+
+    pub fn update_repository_webhook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        webhook_id: u64,
+        webhook: &Webhook,
+    ) -> Result<Webhook, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks/{}",
+            project_key, repository_slug, webhook_id
+        );
+
+        self.send(Method::PUT, &url, Some(webhook))
+    }
+
+    pub fn delete_repository_webhook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        webhook_id: u64,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks/{}",
+            project_key, repository_slug, webhook_id
+        );
+
+        self.delete(&url)
+    }
+
+    pub fn repository_webhook_statistics(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        webhook_id: u64,
+    ) -> Result<WebhookStatistics, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks/{}/statistics",
+            project_key, repository_slug, webhook_id
+        );
+
+        self.get(&url)
+    }
+
+    pub fn test_repository_webhook(
+        &self,
+        project_key: &str,
+        repository_slug: &str,
+        webhook_url: &str,
+    ) -> Result<WebhookTestResult, Error> {
+        let url = format!(
+            "rest/api/1.0/projects/{}/repos/{}/webhooks/test?{}",
+            project_key,
+            repository_slug,
+            test_query(webhook_url)
+        );
+
+        self.post::<(), _>(&url, None)
+    }
+
+    pub fn move_repository_webhooks(
+        &self,
+        project_key: &str,
+        old_url: &str,
+        new_url: &str,
+    ) -> Result<Vec<Webhook>, Error> {
+        let mut moved = Vec::new();
+
+        for repository in self.repositories(project_key)? {
+            for mut webhook in self.repository_webhooks(project_key, repository.slug())? {
+                if webhook.url != old_url {
+                    continue;
+                }
+
+                if let Some(id) = webhook.id {
+                    println!("Moving {} in {}", webhook, repository);
+                    webhook.url = new_url.to_owned();
+                    moved.push(self.update_repository_webhook(
+                        project_key,
+                        repository.slug(),
+                        id,
+                        &webhook,
+                    )?);
+                }
+            }
+        }
+
+        Ok(moved)
+    }
+}
@@ -0,0 +1,260 @@
+use failure::Error;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use webhook::event::WebhookEvent;
+
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+const TIMEOUT_SECS: u64 = 30;
+
+type Handler = Box<dyn Fn(&WebhookEvent) -> Result<(), Error> + Send + Sync>;
+
+pub struct WebhookReceiver {
+    secret: Option<String>,
+    handlers: Vec<Handler>,
+}
+
+impl WebhookReceiver {
+    pub fn new(secret: Option<&str>) -> WebhookReceiver {
+        WebhookReceiver {
+            secret: secret.map(str::to_owned),
+            handlers: Vec::new(),
+        }
+    }
+
+    pub fn handler<F>(mut self, handler: F) -> WebhookReceiver
+    where
+        F: Fn(&WebhookEvent) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Verifies and parses a single delivery, then passes it to every
+    /// handler. Header names are matched case-insensitively.
+    pub fn receive(
+        &self,
+        headers: &HashMap<String, String>,
+        body: &[u8],
+    ) -> Result<WebhookEvent, Error> {
+        let event = self.accept(headers, body)?;
+        self.dispatch(&event)?;
+
+        Ok(event)
+    }
+
+    fn accept(
+        &self,
+        headers: &HashMap<String, String>,
+        body: &[u8],
+    ) -> Result<WebhookEvent, Error> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        };
+
+        if let Some(ref secret) = self.secret {
+            match header("X-Hub-Signature") {
+                Some(signature) => verify_signature(secret, body, signature)?,
+                None => bail!("missing X-Hub-Signature header"),
+            }
+        }
+
+        let event_key = match header("X-Event-Key") {
+            Some(event_key) => event_key,
+            None => bail!("missing X-Event-Key header"),
+        };
+        WebhookEvent::parse(event_key, body)
+    }
+
+    fn dispatch(&self, event: &WebhookEvent) -> Result<(), Error> {
+        for handler in &self.handlers {
+            handler(event)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serves deliveries over plain HTTP, each connection on its own thread.
+    /// Request bodies must carry a `Content-Length`; chunked bodies are not
+    /// supported and are rejected.
+    pub fn listen<A: ToSocketAddrs>(self, addr: A) -> Result<(), Error> {
+        self.listen_on(TcpListener::bind(addr)?)
+    }
+
+    /// Serves deliveries from an already bound listener. A failure on one
+    /// connection is logged and does not stop the receiver.
+    pub fn listen_on(self, listener: TcpListener) -> Result<(), Error> {
+        let receiver = Arc::new(self);
+
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Failed to accept webhook connection: {}", e);
+                    continue;
+                }
+            };
+
+            let receiver = receiver.clone();
+            thread::spawn(move || {
+                if let Err(e) = receiver.respond(stream) {
+                    warn!("Failed to answer webhook delivery: {}", e);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> Result<(), Error> {
+        let timeout = Some(Duration::from_secs(TIMEOUT_SECS));
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)?;
+
+        let status = match read_request(&mut stream) {
+            Ok((headers, body)) => match self.accept(&headers, &body) {
+                Ok(event) => match self.dispatch(&event) {
+                    Ok(()) => "204 No Content",
+                    Err(e) => {
+                        warn!("Failed to handle webhook delivery: {}", e);
+                        "500 Internal Server Error"
+                    }
+                },
+                Err(e) => {
+                    warn!("Rejected webhook delivery: {}", e);
+                    "400 Bad Request"
+                }
+            },
+            Err(e) => {
+                warn!("Unreadable webhook delivery: {}", e);
+                "400 Bad Request"
+            }
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+        )?;
+
+        Ok(())
+    }
+}
+
+fn mac(secret: &str, body: &[u8]) -> Result<Hmac<Sha256>, Error> {
+    let mut mac = match Hmac::<Sha256>::new_varkey(secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => bail!("invalid webhook secret"),
+    };
+    mac.input(body);
+
+    Ok(mac)
+}
+
+pub fn sign(secret: &str, body: &[u8]) -> Result<String, Error> {
+    let digest: String = mac(secret, body)?
+        .result()
+        .code()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    Ok(format!("sha256={}", digest))
+}
+
+/// Checks an `X-Hub-Signature` value in constant time.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> Result<(), Error> {
+    let digest = if signature.starts_with("sha256=") {
+        decode_hex(&signature["sha256=".len()..])
+    } else {
+        None
+    };
+
+    match digest {
+        Some(ref digest) if mac(secret, body)?.verify(digest).is_ok() => Ok(()),
+        _ => bail!("webhook signature does not match"),
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Reads one line of the request head, failing once the head as a whole
+/// would exceed `MAX_HEADER_BYTES`.
+fn read_head_line<R: BufRead>(reader: &mut R, remaining: &mut u64) -> Result<String, Error> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(*remaining).read_line(&mut line)?;
+    *remaining -= read as u64;
+
+    if !line.ends_with('\n') {
+        if *remaining == 0 {
+            bail!("request headers exceed {} bytes", MAX_HEADER_BYTES);
+        }
+        bail!("connection closed before the end of the request headers");
+    }
+
+    Ok(line)
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<(HashMap<String, String>, Vec<u8>), Error> {
+    let mut reader = BufReader::new(stream);
+    let mut remaining = MAX_HEADER_BYTES;
+
+    let request_line = read_head_line(&mut reader, &mut remaining)?;
+    if !request_line.starts_with("POST ") {
+        bail!("unexpected request {}", request_line.trim());
+    }
+
+    let mut headers = HashMap::new();
+    loop {
+        let line = read_head_line(&mut reader, &mut remaining)?;
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(i) = line.find(':') {
+            headers.insert(line[..i].trim().to_owned(), line[i + 1..].trim().to_owned());
+        }
+    }
+
+    let chunked = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("Transfer-Encoding"))
+        .map_or(false, |(_, v)| !v.eq_ignore_ascii_case("identity"));
+    if chunked {
+        bail!("chunked request bodies are not supported");
+    }
+
+    let length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("Content-Length"))
+        .map_or(Ok(0), |(_, v)| v.parse::<usize>())?;
+    if length > MAX_BODY_BYTES {
+        bail!(
+            "request body of {} bytes exceeds {} bytes",
+            length,
+            MAX_BODY_BYTES
+        );
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok((headers, body))
+}
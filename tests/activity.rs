@@ -0,0 +1,158 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use std::time::Duration;
+
+fn user(name: &str, id: u64) -> String {
+    format!(
+        r#"{{
+            "name": "{name}",
+            "emailAddress": "{name}@example.com",
+            "id": {id},
+            "displayName": "{name}",
+            "active": true,
+            "slug": "{name}",
+            "type": "NORMAL"
+        }}"#,
+        name = name,
+        id = id
+    )
+}
+
+#[test]
+fn pull_request_timeline() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/activities(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(format!(
+        r#"{{
+            "size": 6,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {{
+                    "id": 6,
+                    "createdDate": 10000,
+                    "user": {author},
+                    "action": "MERGED"
+                }},
+                {{
+                    "id": 5,
+                    "createdDate": 7000,
+                    "user": {reviewer},
+                    "action": "APPROVED"
+                }},
+                {{
+                    "id": 4,
+                    "createdDate": 5000,
+                    "user": {author},
+                    "action": "RESCOPED",
+                    "fromHash": "abc",
+                    "previousFromHash": "def",
+                    "toHash": "123",
+                    "previousToHash": "123",
+                    "added": {{ "commits": [], "total": 1 }},
+                    "removed": {{ "commits": [], "total": 0 }}
+                }},
+                {{
+                    "id": 3,
+                    "createdDate": 3000,
+                    "user": {reviewer},
+                    "action": "COMMENTED",
+                    "commentAction": "ADDED",
+                    "comment": {{
+                        "id": 1,
+                        "version": 0,
+                        "text": "Looks close",
+                        "author": {reviewer},
+                        "createdDate": 3000,
+                        "updatedDate": 3000
+                    }}
+                }},
+                {{
+                    "id": 2,
+                    "createdDate": 2000,
+                    "user": {author},
+                    "action": "ASSIGNED"
+                }},
+                {{
+                    "id": 1,
+                    "createdDate": 1000,
+                    "user": {author},
+                    "action": "OPENED"
+                }}
+            ],
+            "start": 0
+        }}"#,
+        author = user("author", 1),
+        reviewer = user("reviewer", 2)
+    )).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let timeline = client.pull_request_timeline("FOO", "foo-repo-1", 3)?;
+    assert_eq!(timeline.rescopes, 1);
+    assert_eq!(timeline.time_to_first_review(), Some(Duration::from_millis(2000)));
+    assert_eq!(timeline.time_to_approval(), Some(Duration::from_millis(6000)));
+    assert_eq!(timeline.cycle_time(), Some(Duration::from_millis(9000)));
+
+    Ok(())
+}
+
+#[test]
+fn reopened_pull_request_timeline() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/4/activities(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(format!(
+        r#"{{
+            "size": 3,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {{
+                    "id": 9,
+                    "createdDate": 6000,
+                    "user": {author},
+                    "action": "REOPENED"
+                }},
+                {{
+                    "id": 8,
+                    "createdDate": 4000,
+                    "user": {reviewer},
+                    "action": "DECLINED"
+                }},
+                {{
+                    "id": 7,
+                    "createdDate": 1000,
+                    "user": {author},
+                    "action": "OPENED"
+                }}
+            ],
+            "start": 0
+        }}"#,
+        author = user("author", 1),
+        reviewer = user("reviewer", 2)
+    )).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let timeline = client.pull_request_timeline("FOO", "foo-repo-1", 4)?;
+    assert_eq!(timeline.closed, None);
+    assert_eq!(timeline.cycle_time(), None);
+
+    Ok(())
+}
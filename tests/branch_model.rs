@@ -0,0 +1,107 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::branch_model::{
+    BranchCategory, BranchConfiguration, BranchModelConfiguration, BranchTypeConfiguration,
+};
+
+#[test]
+fn project_branch_model_configuration() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/branch-utils/1.0/projects/FOO/branchmodel/configuration",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "development": { "refId": null, "useDefault": true },
+            "production": { "refId": "refs/heads/master", "useDefault": false },
+            "types": [
+                { "id": "BUGFIX", "displayName": "Bugfix", "enabled": true, "prefix": "bugfix/" },
+                { "id": "FEATURE", "displayName": "Feature", "enabled": true, "prefix": "feature/" },
+                { "id": "HOTFIX", "displayName": "Hotfix", "enabled": false, "prefix": "hotfix/" },
+                { "id": "RELEASE", "displayName": "Release", "enabled": true, "prefix": "release/" }
+            ]
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let configuration = client.project_branch_model_configuration("FOO")?;
+    assert_eq!(configuration.development, BranchConfiguration::default_branch());
+    assert_eq!(configuration.production, Some(BranchConfiguration::branch("master")));
+    assert_eq!(configuration.types.len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn set_repository_branch_model_configuration() -> Result<(), Error> {
+    let body = r#"{
+        "development": { "refId": "refs/heads/develop", "useDefault": false },
+        "production": null,
+        "types": [
+            { "id": "FEATURE", "enabled": true, "prefix": "feat/" }
+        ]
+    }"#;
+
+    let _m = mock(
+        "PUT",
+        "/rest/branch-utils/1.0/projects/FOO/repos/foo-repo-1/branchmodel/configuration",
+    ).match_body(Matcher::JsonString(body.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(body)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let configuration = BranchModelConfiguration {
+        development: BranchConfiguration::branch("develop"),
+        production: None,
+        types: vec![BranchTypeConfiguration::new(BranchCategory::Feature, "feat/")],
+    };
+    assert_eq!(
+        client.set_repository_branch_model_configuration("FOO", "foo-repo-1", &configuration)?,
+        configuration
+    );
+
+    Ok(())
+}
+
+#[test]
+fn repository_branch_model() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/branch-utils/1.0/projects/FOO/repos/foo-repo-1/branchmodel",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "development": {
+                "id": "refs/heads/develop",
+                "displayId": "develop",
+                "type": "BRANCH",
+                "latestCommit": "8d51122def5632836d1cb1026e879069e10a1e13",
+                "latestChangeset": "8d51122def5632836d1cb1026e879069e10a1e13",
+                "isDefault": false
+            },
+            "types": [
+                { "id": "BUGFIX", "displayName": "Bugfix", "prefix": "bugfix/" },
+                { "id": "HOTFIX", "displayName": "Hotfix", "prefix": "bugfix/hot/" }
+            ]
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let model = client.repository_branch_model("FOO", "foo-repo-1")?;
+    assert_eq!(model.classify("bugfix/FOO-1"), Some(BranchCategory::Bugfix));
+    assert_eq!(model.classify("refs/heads/bugfix/hot/FOO-2"), Some(BranchCategory::Hotfix));
+    assert_eq!(model.classify("develop"), None);
+
+    Ok(())
+}
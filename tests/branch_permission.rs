@@ -0,0 +1,294 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::branch_permission::{NewRefRestriction, RestrictionType};
+use thrash::ref_matcher::RefMatcher;
+
+const RESTRICTIONS: &str = r#"{
+    "size": 2,
+    "limit": 25,
+    "isLastPage": true,
+    "values": [
+        {
+            "id": 1,
+            "scope": { "type": "REPOSITORY", "resourceId": 10 },
+            "type": "read-only",
+            "matcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            },
+            "users": [
+                {
+                    "name": "foo-user",
+                    "emailAddress": "foo-user@example.com",
+                    "id": 20,
+                    "displayName": "Foo User",
+                    "active": true,
+                    "slug": "foouser",
+                    "type": "NORMAL"
+                }
+            ],
+            "groups": ["foo-group"],
+            "accessKeys": [
+                {
+                    "key": {
+                        "id": 3,
+                        "text": "ssh-rsa AAAA deploy@example.com",
+                        "label": "deploy"
+                    }
+                }
+            ]
+        },
+        {
+            "id": 2,
+            "scope": { "type": "REPOSITORY", "resourceId": 10 },
+            "type": "no-deletes",
+            "matcher": {
+                "id": "release/*",
+                "displayId": "release/*",
+                "type": { "id": "PATTERN", "name": "Pattern" },
+                "active": true
+            },
+            "users": [],
+            "groups": [],
+            "accessKeys": []
+        }
+    ],
+    "start": 0
+}"#;
+
+#[test]
+fn repository_ref_restrictions() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/branch-permissions/2.0/projects/FOO/repos/foo-repo-1/restrictions(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(RESTRICTIONS)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let restrictions = client.repository_ref_restrictions("FOO", "foo-repo-1")?;
+    assert_eq!(restrictions.len(), 2);
+    assert_eq!(restrictions[0].restriction_type(), RestrictionType::ReadOnly);
+    assert_eq!(restrictions[0].groups(), &["foo-group".to_owned()]);
+    assert_eq!(restrictions[0].access_keys()[0].key().id(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn set_project_ref_restrictions() -> Result<(), Error> {
+    let _current = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/branch-permissions/2.0/projects/BAR/restrictions\?.+$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(RESTRICTIONS)
+    .create();
+
+    let create = mock("POST", "/rest/branch-permissions/2.0/projects/BAR/restrictions")
+        .match_body(Matcher::Regex(r#""type":"pull-request-only""#.to_string()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(
+            r#"{
+                "id": 4,
+                "type": "pull-request-only",
+                "matcher": {
+                    "id": "refs/heads/master",
+                    "displayId": "master",
+                    "type": { "id": "BRANCH", "name": "Branch" },
+                    "active": true
+                }
+            }"#,
+        ).create();
+
+    let delete = mock("DELETE", "/rest/branch-permissions/2.0/projects/BAR/restrictions/2")
+        .with_status(204)
+        .with_header("connection", "close")
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let mut read_only = NewRefRestriction::new(RestrictionType::ReadOnly, RefMatcher::branch("master"));
+    read_only.users = vec!["foo-user".to_owned()];
+    read_only.groups = vec!["foo-group".to_owned()];
+    read_only.access_keys = vec![3];
+
+    client.set_project_ref_restrictions(
+        "BAR",
+        vec![
+            read_only,
+            NewRefRestriction::new(RestrictionType::PullRequestOnly, RefMatcher::branch("master")),
+        ],
+    )?;
+
+    create.assert();
+    delete.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_repository_ref_restrictions_removes_duplicates() -> Result<(), Error> {
+    let _current = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/branch-permissions/2.0/projects/DUP/repos/dup-repo-1/restrictions\?.+$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": 5,
+                    "type": "no-deletes",
+                    "matcher": {
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" },
+                        "active": true
+                    }
+                },
+                {
+                    "id": 6,
+                    "type": "no-deletes",
+                    "matcher": {
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" },
+                        "active": true
+                    }
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/branch-permissions/2.0/projects/DUP/repos/dup-repo-1/restrictions/6",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let kept = mock(
+        "DELETE",
+        "/rest/branch-permissions/2.0/projects/DUP/repos/dup-repo-1/restrictions/5",
+    ).with_status(204)
+    .expect(0)
+    .create();
+
+    let create = mock(
+        "POST",
+        "/rest/branch-permissions/2.0/projects/DUP/repos/dup-repo-1/restrictions",
+    ).with_status(200)
+    .expect(0)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_repository_ref_restrictions(
+        "DUP",
+        "dup-repo-1",
+        vec![NewRefRestriction::new(
+            RestrictionType::NoDeletes,
+            RefMatcher::branch("master"),
+        )],
+    )?;
+
+    delete.assert();
+    kept.assert();
+    create.assert();
+
+    Ok(())
+}
+
+#[test]
+fn update_repository_ref_restriction() -> Result<(), Error> {
+    let _current = mock(
+        "GET",
+        "/rest/branch-permissions/2.0/projects/UPD/repos/upd-repo-1/restrictions/7",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "id": 7,
+            "type": "read-only",
+            "matcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            },
+            "groups": ["foo-group"]
+        }"#,
+    ).create();
+
+    let replace = mock(
+        "POST",
+        "/rest/branch-permissions/2.0/projects/UPD/repos/upd-repo-1/restrictions",
+    ).match_body(Matcher::Regex(r#""type":"fast-forward-only""#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "id": 8,
+            "type": "fast-forward-only",
+            "matcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            }
+        }"#,
+    ).expect(1)
+    .create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/branch-permissions/2.0/projects/UPD/repos/upd-repo-1/restrictions/7",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let current = client.repository_ref_restriction("UPD", "upd-repo-1", 7)?;
+    assert_eq!(current.restriction_type(), RestrictionType::ReadOnly);
+    assert_eq!(current.groups(), &["foo-group".to_owned()]);
+
+    let updated = client.update_repository_ref_restriction(
+        "UPD",
+        "upd-repo-1",
+        current.id(),
+        &NewRefRestriction::new(RestrictionType::FastForwardOnly, RefMatcher::branch("master")),
+    )?;
+    assert_eq!(updated.id(), 8);
+    replace.assert();
+    delete.assert();
+
+    Ok(())
+}
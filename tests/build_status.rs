@@ -0,0 +1,142 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::build_status::{BuildState, BuildStats, BuildStatus};
+
+#[test]
+fn post_build_status() -> Result<(), Error> {
+    let m = mock("POST", "/rest/build-status/1.0/commits/abc123")
+        .match_body(Matcher::JsonString(
+            r#"{
+                "state": "INPROGRESS",
+                "key": "CI-1",
+                "url": "https://ci.example.com/1",
+                "name": "CI #1"
+            }"#.to_string(),
+        )).with_status(204)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let mut status = BuildStatus::new("CI-1", BuildState::InProgress, "https://ci.example.com/1");
+    status.name = Some("CI #1".to_owned());
+    client.post_build_status("abc123", &status)?;
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn post_build_status_rejects_repository_only_states() -> Result<(), Error> {
+    let m = mock("POST", "/rest/build-status/1.0/commits/abc456")
+        .with_status(204)
+        .expect(0)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let status = BuildStatus::new("CI-2", BuildState::Cancelled, "https://ci.example.com/2");
+    assert!(client.post_build_status("abc456", &status).is_err());
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn build_statuses() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/build-status/1.0/commits/abc123(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "state": "SUCCESSFUL",
+                    "key": "CI-1",
+                    "name": "CI #1",
+                    "url": "https://ci.example.com/1",
+                    "description": "Changes by Foo User",
+                    "dateAdded": 1539126000000
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let statuses = client.build_statuses("abc123")?;
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].state, BuildState::Successful);
+
+    Ok(())
+}
+
+#[test]
+fn builds_stats() -> Result<(), Error> {
+    let _m = mock("POST", "/rest/build-status/1.0/commits/stats")
+        .match_body(Matcher::JsonString(r#"["abc123", "def456"]"#.to_string()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "abc123": { "successful": 2, "inProgress": 0, "failed": 1 },
+                "def456": { "successful": 0, "inProgress": 1, "failed": 0, "cancelled": 1 }
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let stats = client.builds_stats(&["abc123", "def456"])?;
+    assert_eq!(
+        stats["abc123"],
+        BuildStats {
+            successful: 2,
+            failed: 1,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        stats["def456"].to_string(),
+        "0 successful, 1 in progress, 0 failed, 1 cancelled, 0 unknown"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn repository_build_status() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/commits/abc123/builds?key=CI-1",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "state": "FAILED",
+            "key": "CI-1",
+            "url": "https://ci.example.com/1",
+            "buildNumber": "1",
+            "dateAdded": 1539126000000
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(
+        client
+            .repository_build_status("FOO", "foo-repo-1", "abc123", "CI-1")?
+            .state,
+        BuildState::Failed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use std::path::PathBuf;
+use thrash::diff::{ChangeType, SegmentType};
+
+#[test]
+fn pull_request_diff() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/diff\?sinceId=abc&untilId=def$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "fromHash": "abc",
+            "toHash": "def",
+            "contextLines": 10,
+            "whitespace": "SHOW",
+            "diffs": [
+                {
+                    "source": {
+                        "components": ["src", "main.rs"],
+                        "parent": "src",
+                        "name": "main.rs",
+                        "extension": "rs",
+                        "toString": "src/main.rs"
+                    },
+                    "destination": {
+                        "components": ["src", "main.rs"],
+                        "parent": "src",
+                        "name": "main.rs",
+                        "extension": "rs",
+                        "toString": "src/main.rs"
+                    },
+                    "hunks": [
+                        {
+                            "sourceLine": 1,
+                            "sourceSpan": 1,
+                            "destinationLine": 1,
+                            "destinationSpan": 2,
+                            "segments": [
+                                {
+                                    "type": "CONTEXT",
+                                    "lines": [
+                                        {
+                                            "source": 1,
+                                            "destination": 1,
+                                            "line": "fn main() {}",
+                                            "truncated": false
+                                        }
+                                    ],
+                                    "truncated": false
+                                },
+                                {
+                                    "type": "ADDED",
+                                    "lines": [
+                                        {
+                                            "source": 1,
+                                            "destination": 2,
+                                            "line": "",
+                                            "truncated": false
+                                        }
+                                    ],
+                                    "truncated": false
+                                }
+                            ],
+                            "truncated": false
+                        }
+                    ],
+                    "truncated": false
+                }
+            ],
+            "truncated": false
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let diff = client.pull_request_diff("FOO", "foo-repo-1", 3, Some("abc"), Some("def"))?;
+    assert_eq!(diff.diffs.len(), 1);
+    assert_eq!(diff.diffs[0].path(), Some(PathBuf::from("src/main.rs")));
+    assert_eq!(
+        diff.diffs[0].hunks[0].segments[1].segment_type,
+        SegmentType::Added
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pull_request_changes() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/changes(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "fromHash": "abc",
+            "toHash": "def",
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "contentId": "111",
+                    "fromContentId": "000",
+                    "path": {
+                        "components": ["README.md"],
+                        "name": "README.md",
+                        "extension": "md",
+                        "toString": "README.md"
+                    },
+                    "executable": false,
+                    "percentUnchanged": -1,
+                    "type": "MODIFY",
+                    "nodeType": "FILE",
+                    "srcExecutable": false
+                },
+                {
+                    "contentId": "222",
+                    "fromContentId": "333",
+                    "path": {
+                        "components": ["src", "lib.rs"],
+                        "toString": "src/lib.rs"
+                    },
+                    "srcPath": {
+                        "components": ["lib.rs"],
+                        "toString": "lib.rs"
+                    },
+                    "percentUnchanged": 100,
+                    "type": "MOVE",
+                    "nodeType": "FILE"
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let changes = client.pull_request_changes("FOO", "foo-repo-1", 3, None, None)?;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].change_type, ChangeType::Move);
+    assert_eq!(changes[1].to_string(), "Move lib.rs -> src/lib.rs");
+
+    Ok(())
+}
+
+#[test]
+fn pull_request_commits() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/commits(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": "def0123456789abcdef0123456789abcdef01234",
+                    "displayId": "def01234567",
+                    "author": {
+                        "name": "foo-user",
+                        "emailAddress": "foo-user@example.com"
+                    },
+                    "authorTimestamp": 1539126000000,
+                    "committer": {
+                        "name": "foo-user",
+                        "emailAddress": "foo-user@example.com"
+                    },
+                    "committerTimestamp": 1539126000000,
+                    "message": "Add main\n\nWith a body",
+                    "parents": [
+                        {
+                            "id": "abc0123456789abcdef0123456789abcdef01234",
+                            "displayId": "abc01234567"
+                        }
+                    ]
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let commits = client.pull_request_commits("FOO", "foo-repo-1", 3)?;
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].to_string(), "def01234567 Add main");
+
+    Ok(())
+}
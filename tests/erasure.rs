@@ -0,0 +1,148 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher, Mock};
+use thrash::erasure::{ErasedUser, ErasureOutcome};
+
+fn admin_user(name: &str, active: bool) -> Mock {
+    mock(
+        "GET",
+        Matcher::Regex(format!(r"^/rest/api/1.0/admin/users\?filter={}(&.+)?$", name)),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(format!(
+        r#"{{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {{
+                    "name": "{name}",
+                    "emailAddress": "{name}@example.com",
+                    "id": 20,
+                    "displayName": "{name}",
+                    "active": {active},
+                    "slug": "{name}",
+                    "type": "NORMAL"
+                }}
+            ],
+            "start": 0
+        }}"#,
+        name = name,
+        active = active
+    )).create()
+}
+
+fn erasure(method: &str, name: &str, status: usize, body: &str) -> Mock {
+    mock(method, format!("/rest/api/1.0/admin/users/erasure?name={}", name).as_str())
+        .with_status(status)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn erase_users() -> Result<(), Error> {
+    let _gone = admin_user("gone", false);
+    let _stuck = admin_user("stuck", false);
+    let _active = admin_user("active", true);
+
+    let _check_gone = erasure("GET", "gone", 200, r#"{ "newIdentifier": "user-8d2ca0e1" }"#);
+    let _check_stuck = erasure(
+        "GET",
+        "stuck",
+        409,
+        r#"{
+            "errors": [
+                {
+                    "context": null,
+                    "message": "The user is the sole admin of a project.",
+                    "exceptionName": "com.atlassian.bitbucket.user.UserErasureException"
+                }
+            ]
+        }"#,
+    );
+
+    let dry_erase = erasure("POST", "gone", 200, r#"{ "newIdentifier": "user-8d2ca0e1" }"#).expect(0);
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let report = client.erase_users(&["gone", "stuck", "active"], true);
+    assert!(report.dry_run);
+    assert_eq!(report.results[0].outcome, ErasureOutcome::Erasable);
+    assert_eq!(report.blocked().count(), 2);
+    match report.results[1].outcome {
+        ErasureOutcome::Blocked(ref errors) => {
+            assert_eq!(errors[0].message, "The user is the sole admin of a project.")
+        }
+        ref outcome => panic!("expected stuck to be blocked, got {:?}", outcome),
+    }
+    dry_erase.assert();
+
+    let erase = erasure("POST", "gone", 200, r#"{ "newIdentifier": "user-8d2ca0e1" }"#).expect(1);
+
+    let report = client.erase_users(&["gone", "stuck", "active"], false);
+    assert_eq!(
+        report.results[0].outcome,
+        ErasureOutcome::Erased(ErasedUser {
+            new_identifier: "user-8d2ca0e1".to_owned()
+        })
+    );
+    assert_eq!(report.blocked().count(), 2);
+    erase.assert();
+
+    Ok(())
+}
+
+#[test]
+fn erase_users_records_failures() -> Result<(), Error> {
+    let _missing = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/users\?filter=missing(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(r#"{ "size": 0, "limit": 25, "isLastPage": true, "values": [], "start": 0 }"#)
+    .create();
+    let _broken = admin_user("broken", false);
+    let _left = admin_user("left", false);
+
+    let _check_broken = erasure("GET", "broken", 200, r#"{ "newIdentifier": "user-0b5e12aa" }"#);
+    let _check_left = erasure("GET", "left", 200, r#"{ "newIdentifier": "user-1e7f9c3d" }"#);
+    let _erase_broken = erasure("POST", "broken", 500, "");
+    let erase_left = erasure("POST", "left", 200, r#"{ "newIdentifier": "user-1e7f9c3d" }"#).expect(1);
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let report = client.erase_users(&["missing", "broken", "left"], false);
+    assert_eq!(report.results[0].outcome, ErasureOutcome::NotFound);
+    match report.results[1].outcome {
+        ErasureOutcome::Failed(_) => {}
+        ref outcome => panic!("expected broken to fail, got {:?}", outcome),
+    }
+    assert_eq!(
+        report.results[2].outcome,
+        ErasureOutcome::Erased(ErasedUser {
+            new_identifier: "user-1e7f9c3d".to_owned()
+        })
+    );
+    assert_eq!(report.failed().count(), 2);
+    erase_left.assert();
+
+    Ok(())
+}
+
+#[test]
+fn erase_active_user() -> Result<(), Error> {
+    let _active = admin_user("still-here", true);
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert!(client.erase_user("still-here").is_err());
+
+    Ok(())
+}
@@ -9,7 +9,7 @@ use mockito::{mock, Matcher};
 fn groups_admin() -> Result<(), Error> {
     let _m = mock(
         "GET",
-        Matcher::Regex(r"^/rest/api/1.0/admin/groups(\?.+)?$".to_string()),
+        Matcher::Regex(r"^/rest/api/1.0/admin/groups\?limit=\d+&start=\d+$".to_string()),
     ).with_status(200)
     .with_header("content-type", "application/json")
     .with_body(
@@ -33,7 +33,9 @@ fn groups_admin() -> Result<(), Error> {
 
     let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
 
-    assert_eq!(client.groups_admin()?.len(), 2);
+    let groups = client.groups_admin(None)?;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].deletable(), Some(true));
 
     Ok(())
 }
@@ -58,9 +60,210 @@ fn groups() -> Result<(), Error> {
         }"#,
     ).create();
 
+    let _page = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/groups\?filter=group&limit=2&start=2$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 2,
+            "isLastPage": false,
+            "values": [
+                "baz-group",
+                "qux-group"
+            ],
+            "start": 2,
+            "nextPageStart": 4
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let groups = client.groups(None)?;
+    assert_eq!(
+        groups.iter().map(|g| g.name()).collect::<Vec<_>>(),
+        vec!["foo-group", "bar-group"]
+    );
+    assert_eq!(groups[0].deletable(), None);
+
+    let page = client.groups_page(Some("group"), 2, 2)?;
+    assert_eq!(
+        page.values.iter().map(|g| g.name()).collect::<Vec<_>>(),
+        vec!["baz-group", "qux-group"]
+    );
+    assert!(!page.is_last_page);
+    assert_eq!(page.next_page_start, Some(4));
+
+    Ok(())
+}
+
+#[test]
+fn groups_filtered() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/groups\?filter=foo&limit=1000&start=0$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "name": "foo-group",
+                    "deletable": false
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let groups = client.groups_admin(Some("foo"))?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].deletable(), Some(false));
+
+    Ok(())
+}
+
+#[test]
+fn create_group() -> Result<(), Error> {
+    let m = mock("POST", "/rest/api/1.0/admin/groups?name=foo+team")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "name": "foo team", "deletable": true }"#)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(client.create_group("foo team")?.name(), "foo team");
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn delete_group() -> Result<(), Error> {
+    let m = mock("DELETE", "/rest/api/1.0/admin/groups?name=foo+team")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "name": "foo team", "deletable": true }"#)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(client.delete_group("foo team")?.name(), "foo team");
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn user_groups() -> Result<(), Error> {
+    let m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/admin/users/more-members\?context=foo%2Buser&limit=1000&start=0$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                { "name": "foo-group" },
+                { "name": "bar-group" }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let groups = client.user_groups("foo+user")?;
+    assert_eq!(
+        groups.iter().map(|g| g.name()).collect::<Vec<_>>(),
+        vec!["foo-group", "bar-group"]
+    );
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_group_members() -> Result<(), Error> {
+    let _members = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/groups/more-members\?context=foo-group(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "name": "foo-user",
+                    "emailAddress": "foo-user@example.com",
+                    "id": 20,
+                    "displayName": "Foo User",
+                    "active": true,
+                    "slug": "foouser",
+                    "type": "NORMAL",
+                    "directoryName": "Stash Internal Directory",
+                    "deletable": true,
+                    "mutableDetails": true,
+                    "mutableGroups": true
+                },
+                {
+                    "name": "bar-user",
+                    "emailAddress": "bar-user@example.com",
+                    "id": 21,
+                    "displayName": "Bar User",
+                    "active": true,
+                    "slug": "baruser",
+                    "type": "NORMAL",
+                    "directoryName": "Stash Internal Directory",
+                    "deletable": true,
+                    "mutableDetails": true,
+                    "mutableGroups": true
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let remove = mock("POST", "/rest/api/1.0/admin/groups/remove-user")
+        .match_body(Matcher::JsonString(
+            r#"{ "context": "foo-group", "itemName": "foo-user" }"#.to_string(),
+        )).with_status(200)
+        .with_header("connection", "close")
+        .expect(1)
+        .create();
+
+    let add = mock("POST", "/rest/api/1.0/admin/groups/add-users")
+        .match_body(Matcher::JsonString(
+            r#"{ "group": "foo-group", "users": ["baz-user"] }"#.to_string(),
+        )).with_status(200)
+        .with_header("connection", "close")
+        .expect(1)
+        .create();
+
     let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
 
-    assert_eq!(client.groups()?.len(), 2);
+    client.set_group_members("foo-group", &["bar-user", "baz-user"])?;
+    remove.assert();
+    add.assert();
 
     Ok(())
 }
@@ -0,0 +1,170 @@
+extern crate failure;
+extern crate mockito;
+extern crate serde_json;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use serde_json::Value;
+use thrash::hook::{BuiltinHook, HookType};
+use thrash::merging::{HookCount, RequiredApproversSettings, RequiredBuildsSettings};
+
+const FORCE_PUSH_HOOK: &str = r#"{
+    "details": {
+        "key": "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:force-push-hook",
+        "name": "Reject Force Push",
+        "type": "PRE_RECEIVE",
+        "description": "Reject all force pushes (git push --force) to this repository",
+        "version": "5.16.0",
+        "configFormKey": null
+    },
+    "enabled": true,
+    "configured": true,
+    "scope": {
+        "type": "PROJECT",
+        "resourceId": 1
+    }
+}"#;
+
+#[test]
+fn repository_hooks() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/hooks\?type=PRE_RECEIVE(&.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(format!(
+        r#"{{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [{}],
+            "start": 0
+        }}"#,
+        FORCE_PUSH_HOOK
+    )).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let hooks = client.repository_hooks("FOO", "foo-repo-1", Some(HookType::PreReceive))?;
+    assert_eq!(hooks.len(), 1);
+    assert_eq!(hooks[0].key(), BuiltinHook::RejectForcePush.key());
+    assert_eq!(hooks[0].hook_type(), HookType::PreReceive);
+    assert!(hooks[0].enabled());
+    assert!(hooks[0].is_inherited());
+    assert!(!hooks[0].is_configurable());
+
+    Ok(())
+}
+
+#[test]
+fn enable_builtin_hook() -> Result<(), Error> {
+    let m = mock(
+        "PUT",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/hooks/com.atlassian.bitbucket.server.bitbucket-bundled-hooks:force-push-hook/enabled",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(FORCE_PUSH_HOOK)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let hook = client.enable_builtin_hook("FOO", "foo-repo-1", BuiltinHook::RejectForcePush)?;
+    assert_eq!(hook.name(), "Reject Force Push");
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn repository_hook_settings() -> Result<(), Error> {
+    let _unconfigured = mock(
+        "GET",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/hooks/example:unconfigured/settings",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .create();
+
+    let _configured = mock(
+        "GET",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/hooks/example:configured/settings",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(r#"{ "branches": "master" }"#)
+    .create();
+
+    let set = mock(
+        "PUT",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/hooks/example:configured/settings",
+    ).match_body(Matcher::JsonString(r#"{ "branches": "master develop" }"#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(r#"{ "branches": "master develop" }"#)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(
+        client.repository_hook_settings::<Value>("FOO", "foo-repo-1", "example:unconfigured")?,
+        None
+    );
+
+    let mut settings: Value = client
+        .repository_hook_settings("FOO", "foo-repo-1", "example:configured")?
+        .unwrap();
+    assert_eq!(settings["branches"], "master");
+
+    settings["branches"] = Value::from("master develop");
+    client.set_repository_hook_settings("FOO", "foo-repo-1", "example:configured", &settings)?;
+    set.assert();
+
+    Ok(())
+}
+
+#[test]
+fn builtin_hook_settings() -> Result<(), Error> {
+    let _approvers = mock(
+        "GET",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-2/settings/hooks/com.atlassian.bitbucket.server.bitbucket-bundled-hooks:requiredApproversMergeHook/settings",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(r#"{ "enable": true, "requiredCount": "2" }"#)
+    .create();
+
+    let builds = mock(
+        "PUT",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-2/settings/hooks/com.atlassian.bitbucket.server.bitbucket-build:requiredBuildsMergeCheck/settings",
+    ).match_body(Matcher::JsonString(r#"{ "requiredCount": "1" }"#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(r#"{ "requiredCount": "1" }"#)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(
+        client.builtin_hook_settings::<RequiredApproversSettings>("FOO", "foo-repo-2")?,
+        Some(RequiredApproversSettings {
+            enable: true,
+            required_count: HookCount(2),
+        })
+    );
+
+    client.set_builtin_hook_settings(
+        "FOO",
+        "foo-repo-2",
+        &RequiredBuildsSettings {
+            required_count: HookCount(1),
+        },
+    )?;
+    builds.assert();
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+extern crate failure;
+extern crate mockito;
+#[macro_use]
+extern crate serde_json;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::insights::{
+    Annotation, AnnotationSeverity, DataType, InsightReport, ReportData, ReportResult,
+};
+
+#[test]
+fn set_insight_report() -> Result<(), Error> {
+    let _m = mock(
+        "PUT",
+        "/rest/insights/1.0/projects/FOO/repos/foo-repo-1/commits/abc123/reports/lint",
+    ).match_body(Matcher::JsonString(
+        r#"{
+            "title": "Lint",
+            "result": "FAIL",
+            "data": [
+                { "title": "Issues", "type": "NUMBER", "value": 3 }
+            ],
+            "reporter": "linter"
+        }"#.to_string(),
+    )).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "key": "lint",
+            "title": "Lint",
+            "result": "FAIL",
+            "data": [
+                { "title": "Issues", "type": "NUMBER", "value": 3 }
+            ],
+            "reporter": "linter",
+            "createdDate": 1539126000000
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let report = InsightReport {
+        title: "Lint".to_owned(),
+        result: Some(ReportResult::Fail),
+        data: vec![ReportData::new("Issues", DataType::Number, json!(3))],
+        reporter: Some("linter".to_owned()),
+        ..Default::default()
+    };
+    let created = client.set_insight_report("FOO", "foo-repo-1", "abc123", "lint", &report)?;
+    assert_eq!(created.key, Some("lint".to_owned()));
+    assert_eq!(created.data, report.data);
+
+    Ok(())
+}
+
+#[test]
+fn add_insight_annotations() -> Result<(), Error> {
+    let m = mock(
+        "POST",
+        "/rest/insights/1.0/projects/FOO/repos/foo-repo-1/commits/abc123/reports/lint/annotations",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .expect(2)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let annotations: Vec<Annotation> = (1..1002)
+        .map(|line| Annotation::new("src/lib.rs", line, "Unused import", AnnotationSeverity::Low))
+        .collect();
+    client.add_insight_annotations("FOO", "foo-repo-1", "abc123", "lint", &annotations)?;
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn insight_annotations() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/insights/1.0/projects/FOO/repos/foo-repo-1/commits/abc123/reports/lint/annotations",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "annotations": [
+                {
+                    "externalId": "1",
+                    "path": "src/lib.rs",
+                    "line": 4,
+                    "message": "Unused import",
+                    "severity": "LOW",
+                    "type": "CODE_SMELL",
+                    "reportKey": "lint"
+                }
+            ],
+            "totalCount": 1
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let annotations = client.insight_annotations("FOO", "foo-repo-1", "abc123", "lint")?;
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].to_string(), "src/lib.rs:4 Unused import");
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+
+const WEAK_RSA: &str = "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAgQC9nHMehKx0vDwtI5NXTVAdZbIfT+eiSk7FnFa68UWLa61nLhkhAQ6IrQrL2xQ7lmOjMUgPXayxNRcdvRCD0KWguwGckIy1xrysmnmKeKNCH4MZ4n7D/Ue9qVZ94fPdUheC6Z5Vj3jwzAypXOVqV6PF0DSrW+v89QVM7L/YDJIp5Q== weak@example.com";
+const ED25519: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIPAQpP9O5h3+00UQHoW+aT2YcfUcfwF3STuwmtuvgVtJ ok@example.com";
+
+fn ssh_keys_body(keys: &[(u64, &str)]) -> String {
+    let values: Vec<String> = keys
+        .iter()
+        .map(|(id, text)| format!(r#"{{ "id": {}, "text": "{}", "label": "key-{}" }}"#, id, text, id))
+        .collect();
+
+    format!(
+        r#"{{
+            "size": {},
+            "limit": 25,
+            "isLastPage": true,
+            "values": [{}],
+            "start": 0
+        }}"#,
+        keys.len(),
+        values.join(", ")
+    )
+}
+
+#[test]
+fn user_ssh_keys() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/ssh/1.0/keys\?user=foouser(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(ssh_keys_body(&[(1, WEAK_RSA), (2, ED25519)]))
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let keys = client.user_ssh_keys("foouser")?;
+    assert_eq!(keys.len(), 2);
+
+    assert_eq!(keys[0].algorithm(), Some("ssh-rsa"));
+    assert_eq!(
+        keys[0].fingerprint()?,
+        "SHA256:aG2rpcrd+Q078Njrhu0B24FsPFjkyE8m/qB5AMdurYs"
+    );
+    assert_eq!(keys[0].bit_length()?, Some(1024));
+    assert!(keys[0].is_weak()?);
+
+    assert_eq!(
+        keys[1].fingerprint()?,
+        "SHA256:7Jfz0lGefmvk8GjdVNr0QvAqri2zDvsu4OdWToLMeYg"
+    );
+    assert_eq!(keys[1].bit_length()?, Some(256));
+    assert!(!keys[1].is_weak()?);
+
+    Ok(())
+}
+
+#[test]
+fn ssh_key_audit() -> Result<(), Error> {
+    let _users = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/users(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "name": "foo-user",
+                    "id": 20,
+                    "displayName": "Foo User",
+                    "active": true,
+                    "slug": "foouser",
+                    "type": "NORMAL"
+                },
+                {
+                    "name": "bar-user",
+                    "id": 21,
+                    "displayName": "Bar User",
+                    "active": true,
+                    "slug": "baruser",
+                    "type": "NORMAL"
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let _foo = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/ssh/1.0/keys\?user=foouser(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(ssh_keys_body(&[(1, WEAK_RSA), (2, ED25519)]))
+    .create();
+
+    let _bar = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/ssh/1.0/keys\?user=baruser(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(ssh_keys_body(&[(3, ED25519), (4, "ssh-rsa not-base64!")]))
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let audit = client.ssh_key_audit()?;
+
+    assert_eq!(audit.weak.len(), 1);
+    assert_eq!(audit.weak[0].user_slug, "foouser");
+
+    assert_eq!(audit.duplicates.len(), 1);
+    let mut owners: Vec<&str> = audit.duplicates[0]
+        .1
+        .iter()
+        .map(|k| k.user_slug.as_str())
+        .collect();
+    owners.sort();
+    assert_eq!(owners, vec!["baruser", "foouser"]);
+
+    assert_eq!(audit.invalid.len(), 1);
+    assert_eq!(audit.invalid[0].key.id(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn user_gpg_keys() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/gpg/1.0/keys\?user=foouser(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": "1",
+                    "fingerprint": "4E1243BD22C66E76C2BA9EDDC1F4D5E0A4C3A2F1",
+                    "emailAddress": "foo-user@example.com",
+                    "expiryDate": 1700000000000,
+                    "subKeys": [
+                        {
+                            "fingerprint": "0A46826A5C2C3C1F2A1B9E8D7C6B5A4938271605",
+                            "expiryDate": 1700000000000
+                        }
+                    ]
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let keys = client.user_gpg_keys("foouser")?;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].key_id(), "C1F4D5E0A4C3A2F1");
+    assert_eq!(keys[0].sub_keys().len(), 1);
+    assert_eq!(keys[0].email_address(), Some("foo-user@example.com"));
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::auth::permission::GlobalPermission;
+
+fn user(name: &str, active: bool, last_authentication: Option<u64>) -> String {
+    format!(
+        r#"{{
+            "name": "{name}",
+            "emailAddress": "{name}@example.com",
+            "id": 20,
+            "displayName": "{name}",
+            "active": {active},
+            "slug": "{name}",
+            "type": "NORMAL"{last}
+        }}"#,
+        name = name,
+        active = active,
+        last = match last_authentication {
+            Some(timestamp) => format!(r#", "lastAuthenticationTimestamp": {}"#, timestamp),
+            None => String::new(),
+        }
+    )
+}
+
+fn page(values: &[String]) -> String {
+    format!(
+        r#"{{
+            "size": {},
+            "limit": 25,
+            "isLastPage": true,
+            "values": [{}],
+            "start": 0
+        }}"#,
+        values.len(),
+        values.join(", ")
+    )
+}
+
+#[test]
+fn seat_report() -> Result<(), Error> {
+    // Recent enough to stay within any window these tests use.
+    let recent = 4_102_444_800_000;
+
+    let _license = mock("GET", "/rest/api/1.0/admin/license")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(
+            r#"{
+                "creationDate": 1500000000000,
+                "purchaseDate": 1500000000000,
+                "expiryDate": 1900000000000,
+                "maintenanceExpiryDate": 1900000000000,
+                "maximumNumberOfUsers": 10,
+                "unlimitedNumberOfUsers": false,
+                "serverId": "BMYD-0000-0000-0000",
+                "supportEntitlementNumber": "SEN-000000",
+                "status": {
+                    "serverId": "BMYD-0000-0000-0000",
+                    "currentNumberOfUsers": 3
+                }
+            }"#,
+        ).create();
+
+    let _licensed = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/users\?permission=LICENSED_USER(&.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(page(&[
+        user("active", true, None),
+        user("stale", true, None),
+        user("never", true, None),
+    ])).create();
+
+    let _users = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/users(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(page(&[
+        user("active", true, Some(recent)),
+        user("stale", true, Some(1_500_000_000_000)),
+        user("never", true, None),
+        user("unlicensed", true, None),
+        user("gone", false, Some(1_500_000_000_000)),
+    ])).create();
+
+    let _grants = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/admin/permissions/users(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(page(&[
+        format!(r#"{{ "user": {}, "permission": "SYS_ADMIN" }}"#, user("active", true, None)),
+        format!(r#"{{ "user": {}, "permission": "PROJECT_CREATE" }}"#, user("gone", false, None)),
+    ])).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let report = client.seat_report(90)?;
+
+    assert_eq!(report.licensed_users, 3);
+    assert_eq!(report.user_limit, Some(10));
+    assert_eq!(report.available_seats(), Some(7));
+    assert_eq!(
+        report.inactive_users.iter().map(|u| u.slug()).collect::<Vec<_>>(),
+        vec!["stale", "never"]
+    );
+    assert_eq!(report.deactivated_grants.len(), 1);
+    assert_eq!(report.deactivated_grants[0].user().slug(), "gone");
+    assert_eq!(
+        *report.deactivated_grants[0].permission(),
+        GlobalPermission::ProjectCreate
+    );
+
+    let report = client.seat_report(u64::max_value())?;
+    assert_eq!(
+        report.inactive_users.iter().map(|u| u.slug()).collect::<Vec<_>>(),
+        vec!["never"]
+    );
+
+    Ok(())
+}
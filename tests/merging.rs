@@ -0,0 +1,1088 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::merging::{
+    DefaultReviewerCondition, MergeConfigType, MergeStrategyId, PullRequestSettings,
+};
+use thrash::project::ProjectRef;
+use thrash::ref_matcher::RefMatcher;
+use thrash::repository::Repository;
+
+const REQUIRED_APPROVERS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:requiredApproversMergeHook";
+const ALL_APPROVERS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:allApproversMergeHook";
+const INCOMPLETE_TASKS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-bundled-hooks:incomplete-tasks-merge-check";
+const REQUIRED_BUILDS_CHECK: &str =
+    "com.atlassian.bitbucket.server.bitbucket-build:requiredBuildsMergeCheck";
+
+#[test]
+fn project_merge_config() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/settings/pull-requests/git(\?.+)?$".to_string(),
+        ),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "defaultStrategy": {
+                    "description": "Always create a merge commit",
+                    "enabled": true,
+                    "flag": "--no-ff",
+                    "id": "no-ff",
+                    "name": "Merge commit"
+                },
+                "strategies": [
+                    {
+                        "description": "Always create a merge commit",
+                        "enabled": true,
+                        "flag": "--no-ff",
+                        "id": "no-ff",
+                        "name": "Merge commit"
+                    },
+                    {
+                        "description": "Combine all changes into a single commit",
+                        "enabled": true,
+                        "flag": "--squash",
+                        "id": "squash",
+                        "name": "Squash"
+                    },
+                    {
+                        "description": "Fast-forward if possible",
+                        "enabled": false,
+                        "flag": "--ff",
+                        "id": "ff",
+                        "name": "Merge commit if necessary"
+                    }
+                ],
+                "type": "PROJECT"
+            }
+        }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let config = client.project_merge_config("FOO")?;
+    assert_eq!(config.default_strategy(), Some(MergeStrategyId::NoFf));
+    assert_eq!(
+        config.enabled_strategies(),
+        vec![MergeStrategyId::NoFf, MergeStrategyId::Squash]
+    );
+    assert_eq!(config.config_type(), MergeConfigType::Project);
+
+    Ok(())
+}
+
+#[test]
+fn repository_merge_config() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/settings/pull-requests(\?.+)?$"
+                .to_string(),
+        ),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "defaultStrategy": {
+                    "enabled": true,
+                    "id": "squash",
+                    "name": "Squash"
+                },
+                "strategies": [
+                    {
+                        "enabled": true,
+                        "id": "squash",
+                        "name": "Squash"
+                    }
+                ],
+                "type": "PROJECT"
+            },
+            "requiredAllApprovers": false,
+            "requiredAllTasksComplete": true,
+            "requiredApprovers": 1,
+            "requiredSuccessfulBuilds": 0
+        }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let config = client.repository_merge_config("FOO", "foo-repo-1")?;
+    assert!(config.is_inherited_by(MergeConfigType::Repository));
+
+    Ok(())
+}
+
+#[test]
+fn set_project_merge_strategies() -> Result<(), Error> {
+    let _m = mock(
+        "POST",
+        "/rest/api/1.0/projects/BAR/settings/pull-requests/git",
+    )
+    .match_body(Matcher::JsonString(
+        r#"{
+                "mergeConfig": {
+                    "defaultStrategy": { "id": "squash" },
+                    "strategies": [{ "id": "squash" }]
+                }
+            }"#
+        .to_string(),
+    ))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+                "mergeConfig": {
+                    "defaultStrategy": { "enabled": true, "id": "squash" },
+                    "strategies": [{ "enabled": true, "id": "squash" }],
+                    "type": "PROJECT"
+                }
+            }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let config = client.set_project_merge_strategies(
+        "BAR",
+        MergeStrategyId::Squash,
+        &[MergeStrategyId::Squash],
+    )?;
+    assert_eq!(config.enabled_strategies(), vec![MergeStrategyId::Squash]);
+
+    assert!(client
+        .set_project_merge_strategies("BAR", MergeStrategyId::NoFf, &[MergeStrategyId::Squash])
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn enforce_project_merge_strategies() -> Result<(), Error> {
+    let _project = mock(
+        "POST",
+        "/rest/api/1.0/projects/MRG/settings/pull-requests/git",
+    )
+    .match_body(Matcher::JsonString(
+        r#"{
+                "mergeConfig": {
+                    "defaultStrategy": { "id": "no-ff" },
+                    "strategies": [{ "id": "no-ff" }, { "id": "squash" }]
+                }
+            }"#
+        .to_string(),
+    ))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+                "mergeConfig": {
+                    "defaultStrategy": { "enabled": true, "id": "no-ff" },
+                    "strategies": [
+                        { "enabled": true, "id": "no-ff" },
+                        { "enabled": true, "id": "squash" }
+                    ],
+                    "type": "PROJECT"
+                }
+            }"#,
+    )
+    .create();
+
+    let _repositories = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/MRG/repos(\?.+)?$".to_string()),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "slug": "mrg-repo-1",
+                    "id": 20,
+                    "name": "mrg-repo-1",
+                    "scmId": "git",
+                    "state": "AVAILABLE",
+                    "statusMessage": "Available",
+                    "forkable": true,
+                    "project": { "key": "MRG" },
+                    "public": false
+                },
+                {
+                    "slug": "mrg-repo-2",
+                    "id": 21,
+                    "name": "mrg-repo-2",
+                    "scmId": "git",
+                    "state": "AVAILABLE",
+                    "statusMessage": "Available",
+                    "forkable": true,
+                    "project": { "key": "MRG" },
+                    "public": false
+                }
+            ],
+            "start": 0
+        }"#,
+    )
+    .create();
+
+    let _overridden = mock(
+        "GET",
+        "/rest/api/1.0/projects/MRG/repos/mrg-repo-1/settings/pull-requests",
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "defaultStrategy": { "enabled": true, "id": "ff-only" },
+                "strategies": [{ "enabled": true, "id": "ff-only" }],
+                "type": "REPOSITORY"
+            }
+        }"#,
+    )
+    .create();
+
+    let _inherited = mock(
+        "GET",
+        "/rest/api/1.0/projects/MRG/repos/mrg-repo-2/settings/pull-requests",
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "defaultStrategy": { "enabled": true, "id": "no-ff" },
+                "strategies": [{ "enabled": true, "id": "no-ff" }],
+                "type": "PROJECT"
+            }
+        }"#,
+    )
+    .create();
+
+    let inherit = mock(
+        "POST",
+        "/rest/api/1.0/projects/MRG/repos/mrg-repo-1/settings/pull-requests",
+    )
+    .match_body(Matcher::JsonString(r#"{ "mergeConfig": {} }"#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "defaultStrategy": { "enabled": true, "id": "no-ff" },
+                "strategies": [{ "enabled": true, "id": "no-ff" }],
+                "type": "PROJECT"
+            }
+        }"#,
+    )
+    .expect(1)
+    .create();
+
+    let skipped = mock(
+        "POST",
+        "/rest/api/1.0/projects/MRG/repos/mrg-repo-2/settings/pull-requests",
+    )
+    .with_status(200)
+    .expect(0)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.enforce_project_merge_strategies(
+        "MRG",
+        MergeStrategyId::NoFf,
+        &[MergeStrategyId::NoFf, MergeStrategyId::Squash],
+    )?;
+    inherit.assert();
+    skipped.assert();
+
+    Ok(())
+}
+
+fn merge_check(project_key: &str, check: &str, enabled: bool) -> mockito::Mock {
+    mock(
+        "GET",
+        format!("/rest/api/1.0/projects/{}/settings/hooks/{}", project_key, check).as_str(),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(format!(
+        r#"{{
+            "details": {{ "key": "{}", "type": "PRE_PULL_REQUEST_MERGE" }},
+            "enabled": {},
+            "configured": true
+        }}"#,
+        check, enabled
+    )).create()
+}
+
+fn merge_check_settings(project_key: &str, check: &str, body: &str) -> mockito::Mock {
+    mock(
+        "GET",
+        format!(
+            "/rest/api/1.0/projects/{}/settings/hooks/{}/settings",
+            project_key, check
+        ).as_str(),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(body)
+    .create()
+}
+
+#[test]
+fn project_pull_request_settings() -> Result<(), Error> {
+    let _approvers = merge_check("BAZ", REQUIRED_APPROVERS_CHECK, true);
+    let _approvers_settings = merge_check_settings(
+        "BAZ",
+        REQUIRED_APPROVERS_CHECK,
+        r#"{ "enable": true, "requiredCount": "2" }"#,
+    );
+    let _all_approvers = merge_check("BAZ", ALL_APPROVERS_CHECK, false);
+    let _tasks = merge_check("BAZ", INCOMPLETE_TASKS_CHECK, true);
+    let _builds = merge_check("BAZ", REQUIRED_BUILDS_CHECK, true);
+    let _builds_settings =
+        merge_check_settings("BAZ", REQUIRED_BUILDS_CHECK, r#"{ "requiredCount": 1 }"#);
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(
+        client.project_pull_request_settings("BAZ")?,
+        PullRequestSettings {
+            required_approvers: 2,
+            required_all_approvers: false,
+            required_all_tasks_complete: true,
+            required_successful_builds: 1,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_project_pull_request_settings() -> Result<(), Error> {
+    let enable_approvers = mock(
+        "PUT",
+        format!(
+            "/rest/api/1.0/projects/PRS/settings/hooks/{}/enabled",
+            REQUIRED_APPROVERS_CHECK
+        ).as_str(),
+    ).match_body(Matcher::JsonString(
+        r#"{ "enable": true, "requiredCount": "2" }"#.to_string(),
+    )).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body("{}")
+    .expect(1)
+    .create();
+
+    let disable_all_approvers = mock(
+        "DELETE",
+        format!(
+            "/rest/api/1.0/projects/PRS/settings/hooks/{}/enabled",
+            ALL_APPROVERS_CHECK
+        ).as_str(),
+    ).with_status(200)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let enable_tasks = mock(
+        "PUT",
+        format!(
+            "/rest/api/1.0/projects/PRS/settings/hooks/{}/enabled",
+            INCOMPLETE_TASKS_CHECK
+        ).as_str(),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body("{}")
+    .expect(1)
+    .create();
+
+    let disable_builds = mock(
+        "DELETE",
+        format!(
+            "/rest/api/1.0/projects/PRS/settings/hooks/{}/enabled",
+            REQUIRED_BUILDS_CHECK
+        ).as_str(),
+    ).with_status(200)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let _approvers = merge_check("PRS", REQUIRED_APPROVERS_CHECK, true);
+    let _approvers_settings = merge_check_settings(
+        "PRS",
+        REQUIRED_APPROVERS_CHECK,
+        r#"{ "enable": true, "requiredCount": "2" }"#,
+    );
+    let _all_approvers = merge_check("PRS", ALL_APPROVERS_CHECK, false);
+    let _tasks = merge_check("PRS", INCOMPLETE_TASKS_CHECK, true);
+    let _builds = merge_check("PRS", REQUIRED_BUILDS_CHECK, false);
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let settings = PullRequestSettings {
+        required_approvers: 2,
+        required_all_approvers: false,
+        required_all_tasks_complete: true,
+        required_successful_builds: 0,
+    };
+    assert_eq!(
+        client.set_project_pull_request_settings("PRS", &settings)?,
+        settings
+    );
+    enable_approvers.assert();
+    disable_all_approvers.assert();
+    enable_tasks.assert();
+    disable_builds.assert();
+
+    Ok(())
+}
+
+#[test]
+fn repository_pull_request_settings() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/BAZ/repos/baz-repo-1/settings/pull-requests(\?.+)?$"
+                .to_string(),
+        ),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "mergeConfig": {
+                "strategies": [],
+                "type": "DEFAULT"
+            },
+            "requiredAllApprovers": false,
+            "requiredAllTasksComplete": true,
+            "requiredApprovers": 2,
+            "requiredSuccessfulBuilds": 1
+        }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(
+        client.repository_pull_request_settings("BAZ", "baz-repo-1")?,
+        PullRequestSettings {
+            required_approvers: 2,
+            required_all_approvers: false,
+            required_all_tasks_complete: true,
+            required_successful_builds: 1,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn apply_pull_request_settings() -> Result<(), Error> {
+    let _projects = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects(\?.+)?$".to_string()),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "key": "QUX",
+                    "id": 1,
+                    "name": "Qux Project",
+                    "description": "Qux Project Description",
+                    "public": false,
+                    "type": "NORMAL"
+                }
+            ],
+            "start": 0
+        }"#,
+    )
+    .create();
+
+    let _repositories = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/QUX/repos(\?.+)?$".to_string()),
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "slug": "qux-repo-1",
+                    "id": 10,
+                    "name": "qux-repo-1",
+                    "scmId": "git",
+                    "state": "AVAILABLE",
+                    "statusMessage": "Available",
+                    "forkable": true,
+                    "project": { "key": "QUX" },
+                    "public": false
+                }
+            ],
+            "start": 0
+        }"#,
+    )
+    .create();
+
+    let _current = mock(
+        "GET",
+        "/rest/api/1.0/projects/QUX/repos/qux-repo-1/settings/pull-requests",
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "requiredAllApprovers": false,
+            "requiredAllTasksComplete": false,
+            "requiredApprovers": 0,
+            "requiredSuccessfulBuilds": 0
+        }"#,
+    )
+    .create();
+
+    let update = mock(
+        "POST",
+        "/rest/api/1.0/projects/QUX/repos/qux-repo-1/settings/pull-requests",
+    )
+    .match_body(Matcher::JsonString(
+        r#"{
+            "requiredApprovers": 2,
+            "requiredAllApprovers": false,
+            "requiredAllTasksComplete": true,
+            "requiredSuccessfulBuilds": 0
+        }"#
+        .to_string(),
+    ))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "requiredAllApprovers": false,
+            "requiredAllTasksComplete": true,
+            "requiredApprovers": 2,
+            "requiredSuccessfulBuilds": 0
+        }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let settings = PullRequestSettings {
+        required_approvers: 2,
+        required_all_tasks_complete: true,
+        ..Default::default()
+    };
+    let repositories = client.apply_pull_request_settings(|_| true, |_| true, &settings)?;
+    assert_eq!(repositories.len(), 1);
+    update.assert();
+
+    Ok(())
+}
+
+const CONDITIONS: &str = r#"[
+    {
+        "id": 1,
+        "repository": {
+            "slug": "foo-repo-1",
+            "id": 10,
+            "name": "foo-repo-1",
+            "scmId": "git",
+            "state": "AVAILABLE",
+            "statusMessage": "Available",
+            "forkable": true,
+            "project": { "key": "FOO" },
+            "public": false
+        },
+        "sourceRefMatcher": {
+            "active": true,
+            "id": "ANY_REF_MATCHER_ID",
+            "displayId": "ANY_REF_MATCHER_ID",
+            "type": { "id": "ANY_REF", "name": "Any branch" }
+        },
+        "targetRefMatcher": {
+            "active": true,
+            "id": "refs/heads/master",
+            "displayId": "master",
+            "type": { "id": "BRANCH", "name": "Branch" }
+        },
+        "reviewers": [
+            {
+                "name": "foo-user",
+                "emailAddress": "foo-user@example.com",
+                "id": 20,
+                "displayName": "Foo User",
+                "active": true,
+                "slug": "foouser",
+                "type": "NORMAL"
+            }
+        ],
+        "requiredApprovals": 1
+    },
+    {
+        "id": 2,
+        "sourceRefMatcher": {
+            "active": true,
+            "id": "ANY_REF_MATCHER_ID",
+            "displayId": "ANY_REF_MATCHER_ID",
+            "type": { "id": "ANY_REF", "name": "Any branch" }
+        },
+        "targetRefMatcher": {
+            "active": true,
+            "id": "release/*",
+            "displayId": "release/*",
+            "type": { "id": "PATTERN", "name": "Pattern" }
+        },
+        "reviewers": [],
+        "requiredApprovals": 0
+    }
+]"#;
+
+#[test]
+fn default_reviewers() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/default-reviewers/1.0/projects/FOO/repos/foo-repo-1/conditions",
+    )
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(CONDITIONS)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let groups = client.default_reviewers("FOO", "foo-repo-1")?;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].target_ref_matcher().display_id(), "master");
+    assert_eq!(groups[0].reviewers()[0].name(), "foo-user");
+    assert_eq!(groups[0].required_approvals(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn set_project_default_reviewers() -> Result<(), Error> {
+    let _current = mock("GET", "/rest/default-reviewers/1.0/projects/BAR/conditions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(CONDITIONS)
+        .create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/default-reviewers/1.0/projects/BAR/condition/2",
+    )
+    .with_status(204)
+    .with_header("connection", "close")
+    .create();
+
+    let update = mock(
+        "PUT",
+        "/rest/default-reviewers/1.0/projects/BAR/condition/1",
+    )
+    .match_body(Matcher::Regex(r#""requiredApprovals":0"#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+                "id": 1,
+                "sourceRefMatcher": {
+                    "active": true,
+                    "id": "ANY_REF_MATCHER_ID",
+                    "displayId": "ANY_REF_MATCHER_ID",
+                    "type": { "id": "ANY_REF", "name": "Any branch" }
+                },
+                "targetRefMatcher": {
+                    "active": true,
+                    "id": "refs/heads/master",
+                    "displayId": "master",
+                    "type": { "id": "BRANCH", "name": "Branch" }
+                },
+                "reviewers": [],
+                "requiredApprovals": 0
+            }"#,
+    )
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_project_default_reviewers(
+        "BAR",
+        vec![DefaultReviewerCondition {
+            source_matcher: RefMatcher::any_ref(),
+            target_matcher: RefMatcher::branch("master"),
+            reviewers: vec![],
+            required_approvals: 0,
+        }],
+    )?;
+
+    delete.assert();
+    update.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_project_default_reviewers_removes_duplicates() -> Result<(), Error> {
+    let _current = mock("GET", "/rest/default-reviewers/1.0/projects/DUP/conditions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(
+            r#"[
+                {
+                    "id": 3,
+                    "sourceRefMatcher": {
+                        "active": true,
+                        "id": "ANY_REF_MATCHER_ID",
+                        "displayId": "ANY_REF_MATCHER_ID",
+                        "type": { "id": "ANY_REF", "name": "Any branch" }
+                    },
+                    "targetRefMatcher": {
+                        "active": true,
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" }
+                    },
+                    "reviewers": [],
+                    "requiredApprovals": 1
+                },
+                {
+                    "id": 4,
+                    "sourceRefMatcher": {
+                        "active": true,
+                        "id": "ANY_REF_MATCHER_ID",
+                        "displayId": "ANY_REF_MATCHER_ID",
+                        "type": { "id": "ANY_REF", "name": "Any branch" }
+                    },
+                    "targetRefMatcher": {
+                        "active": true,
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" }
+                    },
+                    "reviewers": [],
+                    "requiredApprovals": 0
+                }
+            ]"#,
+        )
+        .create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/default-reviewers/1.0/projects/DUP/condition/3",
+    )
+    .with_status(204)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let kept = mock(
+        "DELETE",
+        "/rest/default-reviewers/1.0/projects/DUP/condition/4",
+    )
+    .with_status(204)
+    .expect(0)
+    .create();
+
+    let update = mock(
+        "PUT",
+        Matcher::Regex(r"^/rest/default-reviewers/1.0/projects/DUP/condition/".to_string()),
+    )
+    .with_status(200)
+    .expect(0)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_project_default_reviewers(
+        "DUP",
+        vec![DefaultReviewerCondition {
+            source_matcher: RefMatcher::any_ref(),
+            target_matcher: RefMatcher::branch("master"),
+            reviewers: vec![],
+            required_approvals: 0,
+        }],
+    )?;
+
+    delete.assert();
+    kept.assert();
+    update.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_default_reviewers_leaves_inherited_conditions() -> Result<(), Error> {
+    let _current = mock(
+        "GET",
+        "/rest/default-reviewers/1.0/projects/INH/repos/inh-repo-1/conditions",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(CONDITIONS)
+    .create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/default-reviewers/1.0/projects/INH/repos/inh-repo-1/condition/1",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let inherited = mock(
+        "DELETE",
+        "/rest/default-reviewers/1.0/projects/INH/repos/inh-repo-1/condition/2",
+    ).with_status(204)
+    .expect(0)
+    .create();
+
+    let create = mock(
+        "POST",
+        "/rest/default-reviewers/1.0/projects/INH/repos/inh-repo-1/condition",
+    ).match_body(Matcher::Regex(r#""id":"release/\*""#.to_string()))
+    .with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "id": 5,
+            "sourceRefMatcher": {
+                "active": true,
+                "id": "ANY_REF_MATCHER_ID",
+                "displayId": "ANY_REF_MATCHER_ID",
+                "type": { "id": "ANY_REF", "name": "Any branch" }
+            },
+            "targetRefMatcher": {
+                "active": true,
+                "id": "release/*",
+                "displayId": "release/*",
+                "type": { "id": "PATTERN", "name": "Pattern" }
+            },
+            "reviewers": [],
+            "requiredApprovals": 0
+        }"#,
+    ).expect(1)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_default_reviewers(
+        "INH",
+        "inh-repo-1",
+        vec![DefaultReviewerCondition {
+            source_matcher: RefMatcher::any_ref(),
+            target_matcher: RefMatcher::pattern("release/*"),
+            reviewers: vec![],
+            required_approvals: 0,
+        }],
+    )?;
+
+    delete.assert();
+    inherited.assert();
+    create.assert();
+
+    Ok(())
+}
+
+#[test]
+fn default_reviewers_preview() -> Result<(), Error> {
+    let _reviewers = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/default-reviewers/1.0/projects/FOO/repos/foo-repo-2/reviewers\?sourceRepoId=10&sourceRefId=refs%2Fheads%2Ffeature%2Ffoo&targetRepoId=11&targetRefId=refs%2Fheads%2Fmaster$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"[
+            {
+                "name": "foo-user",
+                "emailAddress": "foo-user@example.com",
+                "id": 20,
+                "displayName": "Foo User",
+                "active": true,
+                "slug": "foouser",
+                "type": "NORMAL"
+            }
+        ]"#,
+    ).create();
+
+    let branch_model = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/branch-utils/1.0/projects/FOO/repos/[^/]+/branchmodel$".to_string()),
+    ).with_status(200)
+    .expect(0)
+    .create();
+
+    let _conditions = mock(
+        "GET",
+        "/rest/default-reviewers/1.0/projects/FOO/repos/foo-repo-2/conditions",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(CONDITIONS)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let repository = |id, slug: &str| Repository {
+        slug: slug.to_owned(),
+        id,
+        name: slug.to_owned(),
+        scm_id: "git".to_owned(),
+        state: "AVAILABLE".to_owned(),
+        status_message: "Available".to_owned(),
+        forkable: true,
+        project: ProjectRef {
+            key: "FOO".to_owned(),
+        },
+        public: false,
+    };
+
+    let preview = client.default_reviewers_preview(
+        &repository(10, "foo-repo-1"),
+        "feature/foo",
+        &repository(11, "foo-repo-2"),
+        "master",
+    )?;
+    assert_eq!(preview.reviewers.len(), 1);
+    assert_eq!(preview.conditions.len(), 1);
+    assert_eq!(preview.required_approvals(), 1);
+    branch_model.assert();
+
+    Ok(())
+}
+
+#[test]
+fn default_reviewers_preview_uses_source_branch_model() -> Result<(), Error> {
+    let _reviewers = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/default-reviewers/1.0/projects/XRP/repos/xrp-repo-2/reviewers\?.+$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body("[]")
+    .create();
+
+    let source_model = mock(
+        "GET",
+        "/rest/branch-utils/1.0/projects/XRP/repos/xrp-repo-1/branchmodel",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "types": [
+                { "id": "FEATURE", "displayName": "Feature", "prefix": "topic/" }
+            ]
+        }"#,
+    ).expect(1)
+    .create();
+
+    let target_model = mock(
+        "GET",
+        "/rest/branch-utils/1.0/projects/XRP/repos/xrp-repo-2/branchmodel",
+    ).with_status(200)
+    .expect(0)
+    .create();
+
+    let _conditions = mock(
+        "GET",
+        "/rest/default-reviewers/1.0/projects/XRP/repos/xrp-repo-2/conditions",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"[
+            {
+                "id": 6,
+                "sourceRefMatcher": {
+                    "active": true,
+                    "id": "FEATURE",
+                    "displayId": "Feature",
+                    "type": { "id": "MODEL_CATEGORY", "name": "Branching model category" }
+                },
+                "targetRefMatcher": {
+                    "active": true,
+                    "id": "ANY_REF_MATCHER_ID",
+                    "displayId": "ANY_REF_MATCHER_ID",
+                    "type": { "id": "ANY_REF", "name": "Any branch" }
+                },
+                "reviewers": [],
+                "requiredApprovals": 2
+            }
+        ]"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let repository = |id, slug: &str| Repository {
+        slug: slug.to_owned(),
+        id,
+        name: slug.to_owned(),
+        scm_id: "git".to_owned(),
+        state: "AVAILABLE".to_owned(),
+        status_message: "Available".to_owned(),
+        forkable: true,
+        project: ProjectRef {
+            key: "XRP".to_owned(),
+        },
+        public: false,
+    };
+
+    let preview = client.default_reviewers_preview(
+        &repository(20, "xrp-repo-1"),
+        "topic/foo",
+        &repository(21, "xrp-repo-2"),
+        "master",
+    )?;
+    assert_eq!(preview.required_approvals(), 2);
+    source_model.assert();
+    target_model.assert();
+
+    Ok(())
+}
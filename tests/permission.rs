@@ -14,6 +14,7 @@ fn project_default_permission_none() -> Result<(), Error> {
         ),
     ).with_status(200)
     .with_header("content-type", "application/json")
+    .with_header("connection", "close")
     .with_body(r#"{ "permitted": false }"#)
     .create();
 
@@ -24,6 +25,7 @@ fn project_default_permission_none() -> Result<(), Error> {
         ),
     ).with_status(200)
     .with_header("content-type", "application/json")
+    .with_header("connection", "close")
     .with_body(r#"{ "permitted": false }"#)
     .create();
 
@@ -34,6 +36,7 @@ fn project_default_permission_none() -> Result<(), Error> {
         ),
     ).with_status(200)
     .with_header("content-type", "application/json")
+    .with_header("connection", "close")
     .with_body(r#"{ "permitted": false }"#)
     .create();
 
@@ -0,0 +1,93 @@
+extern crate failure;
+extern crate serde_json;
+extern crate thrash;
+
+use failure::Error;
+use thrash::branch_model::{BranchCategory, BranchModel, BranchType, ModelBranch};
+use thrash::ref_matcher::{RefMatcher, RefMatcherKind};
+
+fn branch_model() -> BranchModel {
+    BranchModel {
+        development: Some(ModelBranch {
+            id: "refs/heads/develop".to_owned(),
+            display_id: "develop".to_owned(),
+        }),
+        production: Some(ModelBranch {
+            id: "refs/heads/master".to_owned(),
+            display_id: "master".to_owned(),
+        }),
+        types: vec![BranchType {
+            id: BranchCategory::Feature,
+            display_name: "Feature".to_owned(),
+            prefix: "feature/".to_owned(),
+        }],
+    }
+}
+
+#[test]
+fn ref_matcher_kind() -> Result<(), Error> {
+    let matcher: RefMatcher = serde_json::from_str(
+        r#"{
+            "active": true,
+            "id": "FEATURE",
+            "displayId": "Feature",
+            "type": { "id": "MODEL_CATEGORY", "name": "Branching model category" }
+        }"#,
+    )?;
+
+    assert_eq!(matcher.kind(), RefMatcherKind::ModelCategory);
+    assert!(
+        serde_json::to_string(&matcher)?.contains(r#""type":{"id":"MODEL_CATEGORY","name":"#)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn branch_matches() {
+    let matcher = RefMatcher::branch("master");
+
+    assert!(matcher.matches("master", None));
+    assert!(matcher.matches("refs/heads/master", None));
+    assert!(!matcher.matches("master-2", None));
+}
+
+#[test]
+fn pattern_matches() {
+    let cases = vec![
+        ("*", "master", true),
+        ("PROJECT-*", "PROJECT-1234", true),
+        ("PROJECT-*", "feature/PROJECT-1234", true),
+        ("refs/heads/*", "develop", true),
+        ("refs/heads/*", "feature/foo", false),
+        ("refs/heads/**", "feature/foo", true),
+        ("release/*", "release/1.0", true),
+        ("release/*", "release/1.0/hotfix", false),
+        ("hotfix/", "hotfix/foo/bar", true),
+        ("**/release-??", "release-01", true),
+        ("**/release-??", "release-1", false),
+        ("master", "not-master", false),
+    ];
+
+    for (pattern, branch, expected) in cases {
+        assert_eq!(
+            RefMatcher::pattern(pattern).matches(branch, None),
+            expected,
+            "{} against {}",
+            pattern,
+            branch
+        );
+    }
+}
+
+#[test]
+fn model_matches() {
+    let model = branch_model();
+
+    assert!(RefMatcher::model_category("FEATURE").matches("feature/foo", Some(&model)));
+    assert!(!RefMatcher::model_category("FEATURE").matches("bugfix/foo", Some(&model)));
+    assert!(!RefMatcher::model_category("FEATURE").matches("feature/foo", None));
+    assert!(RefMatcher::model_branch("production").matches("master", Some(&model)));
+    assert!(!RefMatcher::model_branch("development").matches("master", Some(&model)));
+    assert!(RefMatcher::any_ref().matches("anything", None));
+}
@@ -0,0 +1,226 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::ref_matcher::RefMatcher;
+use thrash::required_builds::NewRequiredBuild;
+
+const CONDITIONS: &str = r#"{
+    "size": 1,
+    "limit": 25,
+    "isLastPage": true,
+    "values": [
+        {
+            "id": 7,
+            "buildParentKeys": ["ci-build"],
+            "refMatcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            },
+            "exemptRefMatcher": {
+                "id": "release/*",
+                "displayId": "release/*",
+                "type": { "id": "PATTERN", "name": "Pattern" },
+                "active": true
+            }
+        }
+    ],
+    "start": 0
+}"#;
+
+#[test]
+fn required_builds() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/required-builds/latest/projects/FOO/repos/foo-repo-1/conditions(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(CONDITIONS)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let builds = client.required_builds("FOO", "foo-repo-1")?;
+    assert_eq!(builds.len(), 1);
+    assert_eq!(builds[0].build_parent_keys(), &["ci-build".to_owned()]);
+    assert_eq!(
+        builds[0].exempt_ref_matcher().map(RefMatcher::id),
+        Some("release/*")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_project_required_builds() -> Result<(), Error> {
+    let _repositories = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/BAR/repos(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "slug": "bar-repo-1",
+                    "id": 10,
+                    "name": "bar-repo-1",
+                    "scmId": "git",
+                    "state": "AVAILABLE",
+                    "statusMessage": "Available",
+                    "forkable": true,
+                    "project": { "key": "BAR" },
+                    "public": false
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let _current = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/required-builds/latest/projects/BAR/repos/bar-repo-1/conditions(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(CONDITIONS)
+    .create();
+
+    let update = mock(
+        "PUT",
+        "/rest/required-builds/latest/projects/BAR/repos/bar-repo-1/condition/7",
+    ).match_body(Matcher::Regex(
+        r#""buildParentKeys":\["ci-build","ci-lint"\]"#.to_string(),
+    )).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "id": 7,
+            "buildParentKeys": ["ci-build", "ci-lint"],
+            "refMatcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            }
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_project_required_builds(
+        "BAR",
+        vec![NewRequiredBuild {
+            build_parent_keys: vec!["ci-build".to_owned(), "ci-lint".to_owned()],
+            ref_matcher: RefMatcher::branch("master"),
+            exempt_ref_matcher: Some(RefMatcher::pattern("release/*")),
+        }],
+    )?;
+    update.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_required_builds_removes_duplicates() -> Result<(), Error> {
+    let _current = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/required-builds/latest/projects/DUP/repos/dup-repo-1/conditions(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": 8,
+                    "buildParentKeys": ["ci-build"],
+                    "refMatcher": {
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" },
+                        "active": true
+                    }
+                },
+                {
+                    "id": 9,
+                    "buildParentKeys": ["ci-build"],
+                    "refMatcher": {
+                        "id": "refs/heads/master",
+                        "displayId": "master",
+                        "type": { "id": "BRANCH", "name": "Branch" },
+                        "active": true
+                    }
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let update = mock(
+        "PUT",
+        "/rest/required-builds/latest/projects/DUP/repos/dup-repo-1/condition/8",
+    ).match_body(Matcher::Regex(
+        r#""buildParentKeys":\["ci-build","ci-lint"\]"#.to_string(),
+    )).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "id": 8,
+            "buildParentKeys": ["ci-build", "ci-lint"],
+            "refMatcher": {
+                "id": "refs/heads/master",
+                "displayId": "master",
+                "type": { "id": "BRANCH", "name": "Branch" },
+                "active": true
+            }
+        }"#,
+    ).expect(1)
+    .create();
+
+    let delete = mock(
+        "DELETE",
+        "/rest/required-builds/latest/projects/DUP/repos/dup-repo-1/condition/9",
+    ).with_status(204)
+    .with_header("connection", "close")
+    .expect(1)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_required_builds(
+        "DUP",
+        "dup-repo-1",
+        vec![NewRequiredBuild {
+            build_parent_keys: vec!["ci-build".to_owned(), "ci-lint".to_owned()],
+            ref_matcher: RefMatcher::branch("master"),
+            exempt_ref_matcher: None,
+        }],
+    )?;
+    update.assert();
+    delete.assert();
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher, Mock};
+use thrash::auth::sweep::SweepReason;
+
+fn user(name: &str, active: bool) -> String {
+    format!(
+        r#"{{
+            "name": "{name}",
+            "emailAddress": "{name}@example.com",
+            "id": 20,
+            "displayName": "{name}",
+            "active": {active},
+            "slug": "{name}",
+            "type": "NORMAL"
+        }}"#,
+        name = name,
+        active = active
+    )
+}
+
+fn user_grant(name: &str, active: bool, permission: &str) -> String {
+    format!(r#"{{ "user": {}, "permission": "{}" }}"#, user(name, active), permission)
+}
+
+fn group_grant(name: &str, permission: &str) -> String {
+    format!(r#"{{ "group": {{ "name": "{}" }}, "permission": "{}" }}"#, name, permission)
+}
+
+fn get(path: &str, values: &[String]) -> Mock {
+    mock(
+        "GET",
+        Matcher::Regex(format!(r"^{}(\?.+)?$", path)),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(format!(
+        r#"{{
+            "size": {},
+            "limit": 25,
+            "isLastPage": true,
+            "values": [{}],
+            "start": 0
+        }}"#,
+        values.len(),
+        values.join(", ")
+    )).create()
+}
+
+fn delete(path: &str) -> Mock {
+    mock("DELETE", path)
+        .with_status(204)
+        .with_header("connection", "close")
+        .expect(1)
+        .create()
+}
+
+#[test]
+fn access_sweep() -> Result<(), Error> {
+    let _groups = get("/rest/api/1.0/admin/groups", &[r#"{ "name": "devs", "deletable": true }"#.to_owned()]);
+    let _projects = get(
+        "/rest/api/1.0/projects",
+        &[r#"{ "key": "FOO", "id": 1, "name": "Foo Project", "public": false, "type": "NORMAL" }"#.to_owned()],
+    );
+    let _repositories = get(
+        "/rest/api/1.0/projects/FOO/repos",
+        &[r#"{
+            "slug": "foo-repo-1",
+            "id": 10,
+            "name": "foo-repo-1",
+            "scmId": "git",
+            "state": "AVAILABLE",
+            "statusMessage": "Available",
+            "forkable": true,
+            "project": { "key": "FOO" },
+            "public": false
+        }"#.to_owned()],
+    );
+    let _project_users = get(
+        "/rest/api/1.0/projects/FOO/permissions/users",
+        &[
+            user_grant("alice", true, "PROJECT_WRITE"),
+            user_grant("bob", false, "PROJECT_READ"),
+        ],
+    );
+    let _project_groups = get(
+        "/rest/api/1.0/projects/FOO/permissions/groups",
+        &[group_grant("devs", "PROJECT_READ"), group_grant("ghosts", "PROJECT_READ")],
+    );
+    let _repository_users = get(
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/permissions/users",
+        &[
+            user_grant("alice", true, "REPO_READ"),
+            user_grant("carol", true, "REPO_WRITE"),
+        ],
+    );
+    let _repository_groups = get(
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/permissions/groups",
+        &[group_grant("devs", "REPO_WRITE")],
+    );
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let sweep = client.access_sweep()?;
+
+    assert_eq!(sweep.projects.len(), 1);
+    let project = &sweep.projects[0].plan;
+    assert_eq!(project.users.len(), 1);
+    assert_eq!(project.user_removals.len(), 1);
+    assert_eq!(project.user_removals[0].access.user().name(), "bob");
+    assert_eq!(project.user_removals[0].reason, SweepReason::InactiveUser);
+    assert_eq!(project.group_removals.len(), 1);
+    assert_eq!(project.group_removals[0].access.group().name(), "ghosts");
+    assert_eq!(project.group_removals[0].reason, SweepReason::MissingGroup);
+
+    assert_eq!(sweep.repositories.len(), 1);
+    let repository = &sweep.repositories[0].plan;
+    assert_eq!(repository.user_removals.len(), 1);
+    assert_eq!(repository.user_removals[0].access.user().name(), "alice");
+    assert_eq!(repository.user_removals[0].reason, SweepReason::InheritedFromProject);
+    assert!(repository.group_removals.is_empty());
+
+    let remove_bob = delete("/rest/api/1.0/projects/FOO/permissions/users?name=bob");
+    let remove_ghosts = delete("/rest/api/1.0/projects/FOO/permissions/groups?name=ghosts");
+    let remove_alice = delete("/rest/api/1.0/projects/FOO/repos/foo-repo-1/permissions/users?name=alice");
+
+    // Granted after the sweep ran, so it must survive applying it.
+    let _granted_later = get(
+        "/rest/api/1.0/projects/FOO/permissions/users",
+        &[
+            user_grant("alice", true, "PROJECT_WRITE"),
+            user_grant("bob", false, "PROJECT_READ"),
+            user_grant("dave", true, "PROJECT_READ"),
+        ],
+    );
+    let keep_dave = mock("DELETE", "/rest/api/1.0/projects/FOO/permissions/users?name=dave")
+        .with_status(204)
+        .expect(0)
+        .create();
+
+    client.apply_access_sweep(sweep)?;
+    remove_bob.assert();
+    remove_ghosts.assert();
+    remove_alice.assert();
+    keep_dave.assert();
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::pull_request::task::TaskState;
+
+const TASK: &str = r#"{
+    "anchor": {
+        "id": 1,
+        "type": "COMMENT"
+    },
+    "author": {
+        "name": "foo-user",
+        "emailAddress": "foo-user@example.com",
+        "id": 20,
+        "displayName": "Foo User",
+        "active": true,
+        "slug": "foouser",
+        "type": "NORMAL"
+    },
+    "createdDate": 1539126000000,
+    "id": 5,
+    "permittedOperations": {
+        "deletable": true,
+        "editable": true,
+        "transitionable": true
+    },
+    "state": "RESOLVED",
+    "text": "Fix the typo"
+}"#;
+
+#[test]
+fn pull_request_tasks() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/tasks(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(format!(
+        r#"{{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [{}],
+            "start": 0
+        }}"#,
+        TASK
+    )).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let tasks = client.pull_request_tasks("FOO", "foo-repo-1", 3)?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].comment_id(), 1);
+    assert_eq!(tasks[0].state(), TaskState::Resolved);
+
+    Ok(())
+}
+
+#[test]
+fn pull_request_open_task_count() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(
+            r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/pull-requests/3/tasks/count(\?.+)?$"
+                .to_string(),
+        ),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(r#"{ "open": 2, "resolved": 1 }"#)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(client.pull_request_open_task_count("FOO", "foo-repo-1", 3)?, 2);
+
+    Ok(())
+}
+
+#[test]
+fn create_task() -> Result<(), Error> {
+    let _m = mock("POST", "/rest/api/1.0/tasks")
+        .match_body(Matcher::JsonString(
+            r#"{ "anchor": { "id": 1, "type": "COMMENT" }, "text": "Fix the typo" }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(TASK)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(client.create_task(1, "Fix the typo")?.id(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn resolve_task() -> Result<(), Error> {
+    let _m = mock("PUT", "/rest/api/1.0/tasks/5")
+        .match_body(Matcher::JsonString(
+            r#"{ "id": 5, "state": "RESOLVED" }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(TASK)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert!(!client.resolve_task(5)?.is_open());
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use thrash::auth::token::{NewAccessToken, TokenExpiry, TokenPermission};
+
+const DAY: u64 = 24 * 60 * 60 * 1000;
+const NOW: u64 = 1_540_000_000_000;
+
+#[test]
+fn access_tokens() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/access-tokens/1.0/users/ci-bot(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(format!(
+        r#"{{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {{
+                    "id": "123456789012",
+                    "createdDate": {created},
+                    "lastAuthenticated": {created},
+                    "name": "deploy",
+                    "expiryDate": {soon},
+                    "permissions": ["PROJECT_READ", "REPO_WRITE"],
+                    "user": {{
+                        "name": "ci-bot",
+                        "id": 30,
+                        "displayName": "CI Bot",
+                        "active": true,
+                        "slug": "ci-bot",
+                        "type": "NORMAL"
+                    }}
+                }},
+                {{
+                    "id": "210987654321",
+                    "createdDate": {created},
+                    "name": "read-only",
+                    "permissions": ["REPO_READ"],
+                    "user": {{
+                        "name": "ci-bot",
+                        "id": 30,
+                        "displayName": "CI Bot",
+                        "active": true,
+                        "slug": "ci-bot",
+                        "type": "NORMAL"
+                    }}
+                }}
+            ],
+            "start": 0
+        }}"#,
+        created = NOW - 80 * DAY,
+        soon = NOW + 5 * DAY
+    )).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let tokens = client.access_tokens("ci-bot")?;
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(
+        tokens[0].permissions(),
+        &[TokenPermission::ProjectRead, TokenPermission::RepoWrite]
+    );
+    assert_eq!(tokens[0].expiry(), TokenExpiry::At(NOW + 5 * DAY));
+    assert!(tokens[0].expires_within(7, NOW));
+    assert!(!tokens[0].expires_within(3, NOW));
+    assert!(tokens[0].expires_within(u64::max_value(), NOW));
+    assert_eq!(tokens[1].expiry(), TokenExpiry::Never);
+    assert!(!tokens[1].expires_within(365, NOW));
+
+    Ok(())
+}
+
+#[test]
+fn create_access_token() -> Result<(), Error> {
+    let m = mock("PUT", "/rest/access-tokens/1.0/users/ci-bot")
+        .match_body(Matcher::JsonString(
+            r#"{ "name": "deploy", "permissions": ["REPO_WRITE"], "expiryDays": 90 }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "id": "123456789012",
+                "createdDate": 1540000000000,
+                "name": "deploy",
+                "expiryDays": 90,
+                "expiryDate": 1547776000000,
+                "permissions": ["REPO_WRITE"],
+                "user": {
+                    "name": "ci-bot",
+                    "id": 30,
+                    "displayName": "CI Bot",
+                    "active": true,
+                    "slug": "ci-bot",
+                    "type": "NORMAL"
+                },
+                "token": "MDM0MjM5NDc2MDxxxxxxxxxxxxxxxxxxxxxxxx"
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let mut token = NewAccessToken::new("deploy", &[TokenPermission::RepoWrite]);
+    token.expiry_days = Some(90);
+
+    let created = client.create_access_token("ci-bot", &token)?;
+    assert_eq!(created.id(), "123456789012");
+    assert!(created.token().is_some());
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn revoke_access_token() -> Result<(), Error> {
+    let m = mock("DELETE", "/rest/access-tokens/1.0/users/ci-bot/123456789012")
+        .with_status(204)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.revoke_access_token("ci-bot", "123456789012")?;
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn rotate_access_token_keeps_secret_when_revoke_fails() -> Result<(), Error> {
+    let _tokens = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/access-tokens/1.0/users/rotate-bot(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": "111111111111",
+                    "createdDate": 1540000000000,
+                    "name": "deploy",
+                    "permissions": ["REPO_WRITE"],
+                    "user": {
+                        "name": "rotate-bot",
+                        "id": 31,
+                        "displayName": "Rotate Bot",
+                        "active": true,
+                        "slug": "rotate-bot",
+                        "type": "NORMAL"
+                    }
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let create = mock("PUT", "/rest/access-tokens/1.0/users/rotate-bot")
+        .match_body(Matcher::JsonString(
+            r#"{ "name": "deploy", "permissions": ["REPO_WRITE"], "expiryDays": 30 }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(
+            r#"{
+                "id": "222222222222",
+                "createdDate": 1540000000000,
+                "name": "deploy",
+                "expiryDays": 30,
+                "permissions": ["REPO_WRITE"],
+                "user": {
+                    "name": "rotate-bot",
+                    "id": 31,
+                    "displayName": "Rotate Bot",
+                    "active": true,
+                    "slug": "rotate-bot",
+                    "type": "NORMAL"
+                },
+                "token": "MjIyMjIyMjIyMjIyxxxxxxxxxxxxxxxxxxxxxxxx"
+            }"#,
+        ).create();
+
+    let revoke = mock("DELETE", "/rest/access-tokens/1.0/users/rotate-bot/111111111111")
+        .with_status(500)
+        .with_header("connection", "close")
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let tokens = client.access_tokens("rotate-bot")?;
+    let (created, revoked) = client.rotate_access_token("rotate-bot", &tokens[0], Some(30))?;
+    assert_eq!(created.id(), "222222222222");
+    assert!(created.token().is_some());
+    assert!(revoked.is_err());
+    create.assert();
+    revoke.assert();
+
+    Ok(())
+}
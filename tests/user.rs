@@ -4,6 +4,8 @@ extern crate thrash;
 
 use failure::Error;
 use mockito::{mock, Matcher};
+use thrash::auth::permission::{ProjectPermission, RepositoryPermission};
+use thrash::user::{NewUser, PermissionFilter, UserSearch, UserType};
 
 #[test]
 fn users_admin() -> Result<(), Error> {
@@ -75,7 +77,7 @@ fn users_admin() -> Result<(), Error> {
 fn users() -> Result<(), Error> {
     let _m = mock(
         "GET",
-        Matcher::Regex(r"^/rest/api/1.0/users(\?.+)?$".to_string()),
+        Matcher::Regex(r"^/rest/api/1.0/users\?limit=\d+&start=\d+$".to_string()),
     ).with_status(200)
     .with_header("content-type", "application/json")
     .with_body(format!(
@@ -162,3 +164,264 @@ fn user() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn create_user() -> Result<(), Error> {
+    let m = mock(
+        "POST",
+        Matcher::Regex(
+            r"^/rest/api/1.0/admin/users\?name=new-user&displayName=New\+User&emailAddress=new%2Buser%40example.com&addToDefaultGroup=true&notify=true$"
+                .to_string(),
+        ),
+    ).with_status(204)
+    .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.create_user(&NewUser::new(
+        "new-user",
+        "New User",
+        "new+user@example.com",
+    ))?;
+    m.assert();
+
+    let mut silent = NewUser::new("new-user", "New User", "new-user@example.com");
+    silent.notify = false;
+    assert!(client.create_user(&silent).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn delete_user() -> Result<(), Error> {
+    let m = mock("DELETE", "/rest/api/1.0/admin/users?name=foo-user")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "name": "foo-user",
+                "emailAddress": "foo-user@example.com",
+                "id": 20,
+                "displayName": "Foo User",
+                "active": false,
+                "slug": "foouser",
+                "type": "NORMAL"
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let deleted = client.delete_user("foo-user")?;
+    assert_eq!(deleted.slug(), "foouser");
+    assert_eq!(deleted.email_address(), Some("foo-user@example.com"));
+    assert_eq!(deleted.user_type(), UserType::Normal);
+    assert!(!deleted.active());
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn rename_user() -> Result<(), Error> {
+    let m = mock("POST", "/rest/api/1.0/admin/users/rename")
+        .match_body(Matcher::JsonString(
+            r#"{ "name": "foo-user", "newName": "bar-user" }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "name": "bar-user",
+                "id": 20,
+                "displayName": "Foo User",
+                "active": true,
+                "slug": "bar-user",
+                "type": "NORMAL"
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert_eq!(client.rename_user("foo-user", "bar-user")?.name(), "bar-user");
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn update_user() -> Result<(), Error> {
+    let m = mock("PUT", "/rest/api/1.0/admin/users")
+        .match_body(Matcher::JsonString(
+            r#"{ "name": "foo-user", "email": "foo@example.com" }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "name": "foo-user",
+                "emailAddress": "foo@example.com",
+                "id": 20,
+                "displayName": "Foo User",
+                "active": true,
+                "slug": "foouser",
+                "type": "NORMAL"
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let updated = client.update_user("foo-user", None, Some("foo@example.com"))?;
+    assert_eq!(updated.email_address(), Some("foo@example.com"));
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn set_user_password() -> Result<(), Error> {
+    let m = mock("PUT", "/rest/api/1.0/admin/users/credentials")
+        .match_body(Matcher::JsonString(
+            r#"{
+                "name": "foo-user",
+                "password": "hunter22",
+                "passwordConfirm": "hunter22"
+            }"#.to_string(),
+        )).with_status(204)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.set_user_password("foo-user", "hunter22")?;
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn clear_user_captcha() -> Result<(), Error> {
+    let m = mock("DELETE", "/rest/api/1.0/admin/users/captcha?name=foo+user%2B1")
+        .with_status(204)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    client.clear_user_captcha("foo user+1")?;
+    m.assert();
+
+    Ok(())
+}
+
+#[test]
+fn search_users() -> Result<(), Error> {
+    let query = r"filter=foo&group=foo-group&permission=REPO_WRITE&permission.projectKey=FOO&permission.repositorySlug=foo-repo-1&permission.1=PROJECT_READ&permission.1.projectKey=BAR";
+
+    let first = mock(
+        "GET",
+        Matcher::Regex(format!(r"^/rest/api/1.0/users\?{}&limit=1000&start=0$", query)),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 1,
+            "isLastPage": false,
+            "values": [
+                {
+                    "name": "foo-user",
+                    "emailAddress": "foo-user@example.com",
+                    "id": 20,
+                    "displayName": "Foo User",
+                    "active": true,
+                    "slug": "foouser",
+                    "type": "NORMAL"
+                }
+            ],
+            "start": 0,
+            "nextPageStart": 1
+        }"#,
+    ).create();
+
+    let second = mock(
+        "GET",
+        Matcher::Regex(format!(r"^/rest/api/1.0/users\?{}&limit=1000&start=1$", query)),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 1,
+            "isLastPage": true,
+            "values": [
+                {
+                    "name": "foo-bot",
+                    "id": 21,
+                    "displayName": "Foo Bot",
+                    "active": true,
+                    "slug": "foobot",
+                    "type": "SERVICE"
+                }
+            ],
+            "start": 1
+        }"#,
+    ).create();
+
+    let page = mock(
+        "GET",
+        Matcher::Regex(format!(r"^/rest/api/1.0/users\?{}&limit=1&start=1$", query)),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 1,
+            "isLastPage": true,
+            "values": [
+                {
+                    "name": "foo-bot",
+                    "id": 21,
+                    "displayName": "Foo Bot",
+                    "active": true,
+                    "slug": "foobot",
+                    "type": "SERVICE"
+                }
+            ],
+            "start": 1
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let search = UserSearch {
+        filter: Some("foo".to_owned()),
+        group: Some("foo-group".to_owned()),
+        permissions: vec![
+            PermissionFilter::Repository {
+                project_key: "FOO".to_owned(),
+                repository_slug: "foo-repo-1".to_owned(),
+                permission: RepositoryPermission::Write,
+            },
+            PermissionFilter::Project {
+                project_key: "BAR".to_owned(),
+                permission: ProjectPermission::Read,
+            },
+        ],
+    };
+
+    let users = client.search_users(&search)?;
+    assert_eq!(
+        users.iter().map(|u| u.slug()).collect::<Vec<_>>(),
+        vec!["foouser", "foobot"]
+    );
+    assert_eq!(users[1].user_type(), UserType::Service);
+    first.assert();
+    second.assert();
+
+    let users = client.search_users_page(&search, 1, 1)?;
+    assert!(users.is_last_page);
+    assert_eq!(users.next_page_start, None);
+    assert_eq!(users.values[0].slug(), "foobot");
+    page.assert();
+
+    Ok(())
+}
@@ -0,0 +1,518 @@
+extern crate failure;
+extern crate mockito;
+extern crate thrash;
+
+use failure::Error;
+use mockito::{mock, Matcher};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use thrash::webhook::event::WebhookEvent;
+use thrash::webhook::receiver::{self, WebhookReceiver};
+use thrash::webhook::{Webhook, WebhookEventKey};
+
+#[test]
+fn repository_webhooks() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/FOO/repos/foo-repo-1/webhooks(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": 1,
+                    "name": "ci",
+                    "createdDate": 1539126000000,
+                    "updatedDate": 1539126000000,
+                    "events": ["repo:refs_changed", "pr:opened"],
+                    "configuration": {},
+                    "url": "https://ci.example.com/hook",
+                    "active": true
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let webhooks = client.repository_webhooks("FOO", "foo-repo-1")?;
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].id, Some(1));
+    assert_eq!(
+        webhooks[0].events,
+        vec![WebhookEventKey::RefsChanged, WebhookEventKey::PullRequestOpened]
+    );
+    assert!(webhooks[0].ssl_verification_required);
+
+    Ok(())
+}
+
+#[test]
+fn create_repository_webhook() -> Result<(), Error> {
+    let _m = mock("POST", "/rest/api/1.0/projects/FOO/repos/foo-repo-1/webhooks")
+        .match_body(Matcher::JsonString(
+            r#"{
+                "name": "ci",
+                "url": "https://ci.example.com/hook",
+                "events": ["repo:refs_changed"],
+                "active": true,
+                "configuration": { "secret": "s3cret" },
+                "sslVerificationRequired": true
+            }"#.to_string(),
+        )).with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "id": 2,
+                "name": "ci",
+                "events": ["repo:refs_changed"],
+                "configuration": {},
+                "url": "https://ci.example.com/hook",
+                "active": true,
+                "sslVerificationRequired": true
+            }"#,
+        ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let mut webhook = Webhook::new(
+        "ci",
+        "https://ci.example.com/hook",
+        &[WebhookEventKey::RefsChanged],
+    );
+    webhook.configuration.secret = Some("s3cret".to_owned());
+    assert_eq!(
+        client
+            .create_repository_webhook("FOO", "foo-repo-1", &webhook)?
+            .id,
+        Some(2)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn repository_webhook_statistics() -> Result<(), Error> {
+    let _m = mock(
+        "GET",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/webhooks/1/statistics",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "lastSuccess": {
+                "id": 10,
+                "event": "repo:refs_changed",
+                "duration": 100,
+                "start": 1000,
+                "finish": 1100,
+                "request": { "url": "https://ci.example.com/hook", "method": "POST" },
+                "result": { "description": "200", "outcome": "SUCCESS" }
+            },
+            "lastFailure": {
+                "id": 11,
+                "event": "pr:opened",
+                "duration": 100,
+                "start": 2000,
+                "finish": 2100,
+                "request": { "url": "https://ci.example.com/hook", "method": "POST" },
+                "result": { "description": "500", "outcome": "FAILURE" }
+            }
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let statistics = client.repository_webhook_statistics("FOO", "foo-repo-1", 1)?;
+    assert_eq!(statistics.latest().map(|i| i.result.outcome.as_str()), Some("FAILURE"));
+
+    Ok(())
+}
+
+#[test]
+fn test_repository_webhook() -> Result<(), Error> {
+    let _m = mock(
+        "POST",
+        "/rest/api/1.0/projects/FOO/repos/foo-repo-1/webhooks/test?url=https%3A%2F%2Fci.example.com%2Fhook%3Fbuild%3Da%2Bb%23main",
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_body(
+        r#"{
+            "request": { "url": "https://ci.example.com/hook", "method": "POST" },
+            "response": { "statusCode": 204, "headers": {}, "body": "" }
+        }"#,
+    ).create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    assert!(
+        client
+            .test_repository_webhook(
+                "FOO",
+                "foo-repo-1",
+                "https://ci.example.com/hook?build=a+b#main"
+            )?
+            .is_success()
+    );
+
+    Ok(())
+}
+
+const APPROVED: &str = r#"{
+    "eventKey": "pr:reviewer:approved",
+    "date": "2017-09-19T10:39:36+1000",
+    "actor": {
+        "name": "reviewer",
+        "emailAddress": "reviewer@example.com",
+        "id": 2,
+        "displayName": "Reviewer",
+        "active": true,
+        "slug": "reviewer",
+        "type": "NORMAL"
+    },
+    "pullRequest": {
+        "id": 9,
+        "version": 1,
+        "title": "Add a README",
+        "state": "OPEN",
+        "open": true,
+        "closed": false,
+        "createdDate": 1505781560908,
+        "updatedDate": 1505781560908,
+        "fromRef": {
+            "id": "refs/heads/feature/readme",
+            "displayId": "feature/readme",
+            "latestCommit": "ef8755f06ee4b28c96a847a95cb8ec8ed6ddd1ca",
+            "repository": {
+                "slug": "foo-repo-1",
+                "id": 10,
+                "name": "foo-repo-1",
+                "scmId": "git",
+                "state": "AVAILABLE",
+                "statusMessage": "Available",
+                "forkable": true,
+                "project": { "key": "FOO", "id": 1, "name": "Foo Project", "public": false, "type": "NORMAL" },
+                "public": false
+            }
+        },
+        "toRef": {
+            "id": "refs/heads/master",
+            "displayId": "master",
+            "latestCommit": "178864a7d521b6f5e720b386b2c2b0ef8563e0dc",
+            "repository": {
+                "slug": "foo-repo-1",
+                "id": 10,
+                "name": "foo-repo-1",
+                "scmId": "git",
+                "state": "AVAILABLE",
+                "statusMessage": "Available",
+                "forkable": true,
+                "project": { "key": "FOO", "id": 1, "name": "Foo Project", "public": false, "type": "NORMAL" },
+                "public": false
+            }
+        },
+        "locked": false,
+        "author": {
+            "user": {
+                "name": "author",
+                "emailAddress": "author@example.com",
+                "id": 1,
+                "displayName": "Author",
+                "active": true,
+                "slug": "author",
+                "type": "NORMAL"
+            },
+            "role": "AUTHOR",
+            "approved": false,
+            "status": "UNAPPROVED"
+        },
+        "reviewers": [],
+        "participants": []
+    },
+    "participant": {
+        "user": {
+            "name": "reviewer",
+            "emailAddress": "reviewer@example.com",
+            "id": 2,
+            "displayName": "Reviewer",
+            "active": true,
+            "slug": "reviewer",
+            "type": "NORMAL"
+        },
+        "role": "REVIEWER",
+        "approved": true,
+        "status": "APPROVED"
+    },
+    "previousStatus": "UNAPPROVED"
+}"#;
+
+fn delivery_headers(event_key: &str, signature: &str) -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    headers.insert("X-Event-Key".to_owned(), event_key.to_owned());
+    headers.insert("x-hub-signature".to_owned(), signature.to_owned());
+    headers
+}
+
+#[test]
+fn receive_signed_event() -> Result<(), Error> {
+    let approvals = Arc::new(Mutex::new(Vec::new()));
+    let seen = approvals.clone();
+    let receiver = WebhookReceiver::new(Some("s3cret")).handler(move |event| {
+        if let WebhookEvent::ReviewerApproved(ref e) = *event {
+            seen.lock().unwrap().push(e.pull_request.id);
+        }
+        Ok(())
+    });
+
+    let signature = receiver::sign("s3cret", APPROVED.as_bytes())?;
+    let event = receiver.receive(
+        &delivery_headers("pr:reviewer:approved", &signature),
+        APPROVED.as_bytes(),
+    )?;
+
+    assert_eq!(event.key(), Some(WebhookEventKey::ReviewerApproved));
+    assert_eq!(event.pull_request().map(|pr| pr.title.as_str()), Some("Add a README"));
+    assert_eq!(*approvals.lock().unwrap(), vec![9]);
+
+    Ok(())
+}
+
+#[test]
+fn receive_rejects_bad_signature() {
+    let receiver = WebhookReceiver::new(Some("s3cret"));
+    let signature = receiver::sign("wrong", APPROVED.as_bytes()).unwrap();
+
+    assert!(
+        receiver
+            .receive(
+                &delivery_headers("pr:reviewer:approved", &signature),
+                APPROVED.as_bytes()
+            ).is_err()
+    );
+}
+
+#[test]
+fn receive_ping() -> Result<(), Error> {
+    let body = br#"{"test": true}"#;
+    let signature = receiver::sign("s3cret", body)?;
+
+    match WebhookReceiver::new(Some("s3cret"))
+        .receive(&delivery_headers("diagnostics:ping", &signature), body)?
+    {
+        WebhookEvent::Ping => Ok(()),
+        event => panic!("expected a ping, got {:?}", event),
+    }
+}
+
+fn deliver(addr: SocketAddr, request: &str) -> Result<String, Error> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+#[test]
+fn listen_survives_bad_connections() -> Result<(), Error> {
+    let pings = Arc::new(Mutex::new(0));
+    let seen = pings.clone();
+    let receiver = WebhookReceiver::new(Some("s3cret")).handler(move |event| {
+        if let WebhookEvent::Ping = *event {
+            *seen.lock().unwrap() += 1;
+        }
+        Ok(())
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || receiver.listen_on(listener));
+
+    drop(TcpStream::connect(addr)?);
+
+    assert!(deliver(addr, "GET / HTTP/1.1\r\n\r\n")?.starts_with("HTTP/1.1 400"));
+    assert!(
+        deliver(
+            addr,
+            "POST / HTTP/1.1\r\nContent-Length: 1000000000\r\n\r\n"
+        )?.starts_with("HTTP/1.1 400")
+    );
+
+    let body = r#"{"test": true}"#;
+    let request = format!(
+        "POST / HTTP/1.1\r\nX-Event-Key: diagnostics:ping\r\nX-Hub-Signature: {}\r\nContent-Length: {}\r\n\r\n{}",
+        receiver::sign("s3cret", body.as_bytes())?,
+        body.len(),
+        body
+    );
+    assert!(deliver(addr, &request)?.starts_with("HTTP/1.1 204"));
+    assert_eq!(*pings.lock().unwrap(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn listen_serves_connections_concurrently() -> Result<(), Error> {
+    let receiver = WebhookReceiver::new(None).handler(|event| match *event {
+        WebhookEvent::Ping => Ok(()),
+        _ => Err(failure::err_msg("unexpected event")),
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || receiver.listen_on(listener));
+
+    // Held open without a request, which must not hold up other deliveries.
+    let _stalled = TcpStream::connect(addr)?;
+
+    let ping = r#"{"test": true}"#;
+    let request = format!(
+        "POST / HTTP/1.1\r\nX-Event-Key: diagnostics:ping\r\nContent-Length: {}\r\n\r\n{}",
+        ping.len(),
+        ping
+    );
+    assert!(deliver(addr, &request)?.starts_with("HTTP/1.1 204"));
+
+    let request = format!(
+        "POST / HTTP/1.1\r\nX-Event-Key: pr:reviewer:approved\r\nContent-Length: {}\r\n\r\n{}",
+        APPROVED.len(),
+        APPROVED
+    );
+    assert!(deliver(addr, &request)?.starts_with("HTTP/1.1 500"));
+
+    let request = format!(
+        "POST / HTTP/1.1\r\nX-Event-Key: diagnostics:ping\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+        ping.len(),
+        ping
+    );
+    assert!(deliver(addr, &request)?.starts_with("HTTP/1.1 400"));
+
+    Ok(())
+}
+
+#[test]
+fn move_repository_webhooks() -> Result<(), Error> {
+    let _repositories = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/MOV/repos(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 1,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "slug": "mov-repo-1",
+                    "id": 30,
+                    "name": "mov-repo-1",
+                    "scmId": "git",
+                    "state": "AVAILABLE",
+                    "statusMessage": "Available",
+                    "forkable": true,
+                    "project": { "key": "MOV" },
+                    "public": false
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let _webhooks = mock(
+        "GET",
+        Matcher::Regex(r"^/rest/api/1.0/projects/MOV/repos/mov-repo-1/webhooks(\?.+)?$".to_string()),
+    ).with_status(200)
+    .with_header("content-type", "application/json")
+    .with_header("connection", "close")
+    .with_body(
+        r#"{
+            "size": 2,
+            "limit": 25,
+            "isLastPage": true,
+            "values": [
+                {
+                    "id": 1,
+                    "name": "ci",
+                    "events": ["repo:refs_changed", "repo:modified"],
+                    "configuration": {},
+                    "url": "https://old-ci.example.com/hook",
+                    "active": true,
+                    "sslVerificationRequired": true
+                },
+                {
+                    "id": 2,
+                    "name": "chat",
+                    "events": ["pr:opened"],
+                    "configuration": {},
+                    "url": "https://chat.example.com/hook",
+                    "active": true,
+                    "sslVerificationRequired": true
+                }
+            ],
+            "start": 0
+        }"#,
+    ).create();
+
+    let moved = mock("PUT", "/rest/api/1.0/projects/MOV/repos/mov-repo-1/webhooks/1")
+        .match_body(Matcher::JsonString(
+            r#"{
+                "name": "ci",
+                "url": "https://new-ci.example.com/hook",
+                "events": ["repo:refs_changed", "repo:modified"],
+                "active": true,
+                "configuration": {},
+                "sslVerificationRequired": true
+            }"#.to_string(),
+        )).with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("connection", "close")
+        .with_body(
+            r#"{
+                "id": 1,
+                "name": "ci",
+                "events": ["repo:refs_changed", "repo:modified"],
+                "configuration": {},
+                "url": "https://new-ci.example.com/hook",
+                "active": true,
+                "sslVerificationRequired": true
+            }"#,
+        ).expect(1)
+        .create();
+
+    let untouched = mock("PUT", "/rest/api/1.0/projects/MOV/repos/mov-repo-1/webhooks/2")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let client = thrash::client::Client::new(mockito::SERVER_URL, "user", "password")?;
+
+    let webhooks = client.move_repository_webhooks(
+        "MOV",
+        "https://old-ci.example.com/hook",
+        "https://new-ci.example.com/hook",
+    )?;
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(
+        webhooks[0].events,
+        vec![
+            WebhookEventKey::RefsChanged,
+            WebhookEventKey::Other("repo:modified".to_owned())
+        ]
+    );
+    moved.assert();
+    untouched.assert();
+
+    Ok(())
+}